@@ -0,0 +1,67 @@
+//! Сравнява паметта, която заемат безименните термове с кутии и в арена.
+//!
+//! Паметта се измерва с алокатор, който брои заделените и освободените
+//! байтове, а не се пресмята от размера на типовете.
+
+use hw::arena::Arena;
+use hw::fixtures::tree;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Системният алокатор, който брои заделените в момента байтове и
+/// извършените заделяния.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Изпълнява `f` и връща резултата ѝ заедно с байтовете, които тя е
+/// заделила и не е освободила, и броя на заделянията.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, usize) {
+    let bytes = ALLOCATED.load(Ordering::SeqCst);
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    let result = f();
+
+    (
+        result,
+        ALLOCATED.load(Ordering::SeqCst) - bytes,
+        ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+    )
+}
+
+fn main() {
+    println!(
+        "{:<10} {:>12} {:>12} {:>12} {:>12}",
+        "дълбочина", "box (B)", "заделяния", "arena (B)", "заделяния"
+    );
+
+    for &depth in &[10, 14, 18] {
+        let (term, boxed, boxed_allocations) = measure(|| tree(depth));
+        let (_arena, arena_bytes, arena_allocations) = measure(|| {
+            let mut arena = Arena::new();
+            arena.alloc_unnamed(&term);
+            arena
+        });
+
+        println!(
+            "{:<10} {:>12} {:>12} {:>12} {:>12}",
+            depth, boxed, boxed_allocations, arena_bytes, arena_allocations
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use hw::arena::Arena;
+use hw::fixtures::{factorial, tree};
+use hw::unnamed::{Strategy, Term};
+use hw::{nbe, optimal, parallel};
+
+fn factorial_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("factorial");
+    group.sample_size(10);
+
+    for n in 1..=4 {
+        let term = factorial(n);
+
+        group.bench_with_input(BenchmarkId::new("normal-order", n), &term, |b, term| {
+            b.iter(|| term.normalize(Strategy::NormalOrder, usize::MAX, false))
+        });
+    }
+
+    for n in 1..=6 {
+        let term = factorial(n);
+
+        group.bench_with_input(BenchmarkId::new("nbe", n), &term, |b, term| {
+            b.iter(|| nbe::normalize(term, usize::MAX))
+        });
+    }
+
+    for n in 1..=4 {
+        let term = factorial(n);
+        group.bench_with_input(BenchmarkId::new("optimal", n), &term, |b, term| {
+            b.iter(|| optimal::normalize(term, usize::MAX))
+        });
+    }
+
+    for n in 1..=4 {
+        let term = factorial(n);
+        group.bench_with_input(BenchmarkId::new("parallel", n), &term, |b, term| {
+            b.iter(|| parallel::normalize(term, usize::MAX))
+        });
+    }
+
+    for n in 1..=4 {
+        let term = factorial(n);
+        group.bench_with_input(BenchmarkId::new("arena", n), &term, |b, term| {
+            b.iter(|| {
+                let mut arena = Arena::new();
+                let id = arena.alloc_unnamed(term);
+                arena.normalize(id, usize::MAX)
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn large_term_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large-term");
+    group.sample_size(10);
+
+    let depth = 18;
+    let term = tree(depth);
+    let subs = Term::lambda(Term::var(0));
+
+    group.bench_function("build/box", |b| b.iter(|| tree(depth)));
+    group.bench_function("build/arena", |b| {
+        b.iter(|| {
+            let mut arena = Arena::with_capacity(1 << (depth + 1));
+            arena.alloc_unnamed(&term)
+        })
+    });
+
+    group.bench_function("substitute/box", |b| b.iter(|| term.substitute(0, &subs)));
+
+    let mut arena = Arena::new();
+    let id = arena.alloc_unnamed(&term);
+    let subs_id = arena.alloc_unnamed(&subs);
+    group.bench_function("substitute/arena", |b| {
+        b.iter_batched(
+            || arena.clone(),
+            |mut arena| arena.substitute(id, 0, subs_id),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, factorial_benchmark, large_term_benchmark);
+criterion_main!(benches);
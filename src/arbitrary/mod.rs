@@ -0,0 +1,251 @@
+//! Генератори на случайни термове за тестване на свойства.
+//!
+//! Модулът е достъпен с feature `arbitrary` и съдържа стратегии за
+//! `proptest` и имплементации на `quickcheck::Arbitrary` за именуваните и
+//! безименните термове. Всички генерирани термове, както и всички термове,
+//! получени при смаляването им, са добре дефинирани - свободните им
+//! променливи са сред зададения контекст.
+//!
+//! # Пример
+//!
+//! ```
+//! use hw::arbitrary::named_term;
+//! use hw::unnamed::Term;
+//! use proptest::proptest;
+//!
+//! proptest!(|(term in named_term(&["a", "b"], 6))| {
+//!     let mut names = vec!["a".to_string(), "b".to_string()];
+//!     Term::from_named_with(&term, &mut names);
+//!
+//!     assert_eq!(names.len(), 2);
+//! });
+//! ```
+
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use proptest::collection;
+use proptest::prelude::*;
+use quickcheck::{Arbitrary, Gen};
+
+/// Имената, от които се избират имената на абстракциите в именуваните
+/// термове. Повтарят се, за да има и засенчване.
+const BINDERS: [&str; 3] = ["x", "y", "z"];
+
+/// Стратегия за безименни термове с височина най-много `depth`, чиито
+/// свободни променливи са с индекси по-малки от `context`.
+///
+/// При `context = 0` се генерират само затворени термове. Смаляването
+/// предпочита променливите пред абстракциите и апликациите.
+pub fn unnamed_term(context: usize, depth: usize) -> BoxedStrategy<UnnamedTerm> {
+    // `levels[c]` е стратегията за контекст `context + c` и текущата
+    // височина. Под всяка абстракция контекстът нараства с единица, така че
+    // на височина `height` са нужни контекстите до `context + depth - height`.
+    let mut levels = (0..=depth)
+        .map(|c| match context + c {
+            0 => Just(UnnamedTerm::lambda(UnnamedTerm::var(0))).boxed(),
+            n => (0..n).prop_map(UnnamedTerm::var).boxed(),
+        })
+        .collect::<Vec<_>>();
+
+    for height in 1..=depth {
+        levels = (0..=depth - height)
+            .map(|c| {
+                let lambda = levels[c + 1].clone().prop_map(UnnamedTerm::lambda);
+                let apply = (levels[c].clone(), levels[c].clone())
+                    .prop_map(|(t1, t2)| UnnamedTerm::apply(t1, t2));
+
+                match context + c {
+                    0 => prop_oneof![lambda, apply].boxed(),
+                    n => {
+                        let var = (0..n).prop_map(UnnamedTerm::var);
+                        prop_oneof![2 => var, 1 => lambda, 1 => apply].boxed()
+                    },
+                }
+            })
+            .collect();
+    }
+
+    levels.swap_remove(0)
+}
+
+/// Стратегия за именувани термове с височина най-много `depth`, чиито
+/// свободни променливи са сред `free`.
+///
+/// При празно `free` се генерират само затворени термове.
+pub fn named_term(free: &[&str], depth: usize) -> BoxedStrategy<NamedTerm> {
+    let free = free.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+    (
+        unnamed_term(free.len(), depth),
+        collection::vec(0..BINDERS.len(), depth),
+    )
+        .prop_map(move |(term, binders)| name(&term, &free, &binders, &mut Vec::new()))
+        .boxed()
+}
+
+/// Именува безименен терм. Абстракцията на дълбочина `k` получава името
+/// `BINDERS[binders[k]]`, а свободната променлива `i` - името `free[i]`.
+///
+/// Ако име на абстракция съвпада с име от обхвата ѝ, то се засенчва, но
+/// всички променливи на резултата остават свързани или в `free`.
+fn name(
+    term: &UnnamedTerm,
+    free: &[String],
+    binders: &[usize],
+    scope: &mut Vec<String>,
+) -> NamedTerm {
+    match term {
+        UnnamedTerm::Var(i) if *i < scope.len() => {
+            NamedTerm::var(scope[scope.len() - 1 - i].clone())
+        },
+        UnnamedTerm::Var(i) => NamedTerm::var(free[i - scope.len()].clone()),
+        UnnamedTerm::Apply(t1, t2) => {
+            let t1 = name(t1, free, binders, scope);
+            NamedTerm::apply(t1, name(t2, free, binders, scope))
+        },
+        UnnamedTerm::Lambda(t) => {
+            let x = BINDERS[binders.get(scope.len()).map_or(0, |&b| b)].to_string();
+
+            scope.push(x.clone());
+            let t = name(t, free, binders, scope);
+            scope.pop();
+
+            NamedTerm::lambda(x, t)
+        },
+    }
+}
+
+/// Връща случайно число от `0` до `n` включително.
+fn below(g: &mut Gen, n: usize) -> usize {
+    usize::arbitrary(g) % (n + 1)
+}
+
+/// Генерира безименен терм с около `size` върха, чиито свободни променливи
+/// са с индекси по-малки от `context`.
+fn gen_unnamed(g: &mut Gen, context: usize, size: usize) -> UnnamedTerm {
+    let choice = match (context, size) {
+        (0, _) if size <= 1 => 1,
+        (_, 0) | (_, 1) => 0,
+        (0, _) => 1 + below(g, 1),
+        _ => below(g, 2),
+    };
+
+    match choice {
+        0 => UnnamedTerm::var(below(g, context - 1)),
+        1 => UnnamedTerm::lambda(gen_unnamed(g, context + 1, size.saturating_sub(1))),
+        _ => {
+            let left = below(g, size - 1);
+            let t1 = gen_unnamed(g, context, left);
+            UnnamedTerm::apply(t1, gen_unnamed(g, context, size - 1 - left))
+        },
+    }
+}
+
+/// Връща смалените варианти на безименен терм, чиито свободни променливи
+/// са с индекси не по-големи от тези на терма.
+fn shrink_unnamed(term: &UnnamedTerm) -> Vec<UnnamedTerm> {
+    match term {
+        UnnamedTerm::Var(n) => (0..*n).map(UnnamedTerm::var).collect(),
+        UnnamedTerm::Lambda(t) => {
+            let mut result = Vec::new();
+            if !t.is_free(0) {
+                result.push(t.lower(0));
+            }
+
+            result.extend(shrink_unnamed(t).into_iter().map(UnnamedTerm::lambda));
+            result
+        },
+        UnnamedTerm::Apply(t1, t2) => shrink_apply(t1, t2, shrink_unnamed, UnnamedTerm::apply),
+    }
+}
+
+/// Генерира именуван терм с около `size` върха, чиито свободни променливи
+/// са сред `scope`.
+fn gen_named(g: &mut Gen, scope: &mut Vec<String>, size: usize) -> NamedTerm {
+    let choice = match (scope.is_empty(), size) {
+        (true, _) if size <= 1 => 1,
+        (_, 0) | (_, 1) => 0,
+        (true, _) => 1 + below(g, 1),
+        _ => below(g, 2),
+    };
+
+    match choice {
+        0 => NamedTerm::var(g.choose(scope).unwrap().clone()),
+        1 => {
+            let x = g.choose(&BINDERS).unwrap().to_string();
+
+            scope.push(x.clone());
+            let t = gen_named(g, scope, size.saturating_sub(1));
+            scope.pop();
+
+            NamedTerm::lambda(x, t)
+        },
+        _ => {
+            let left = below(g, size - 1);
+            let t1 = gen_named(g, scope, left);
+            NamedTerm::apply(t1, gen_named(g, scope, size - 1 - left))
+        },
+    }
+}
+
+/// Връща смалените варианти на именуван терм, чиито свободни променливи са
+/// сред свободните променливи на терма.
+fn shrink_named(term: &NamedTerm) -> Vec<NamedTerm> {
+    match term {
+        NamedTerm::Var(_) => Vec::new(),
+        NamedTerm::Lambda(x, t) => {
+            let mut result = Vec::new();
+            if !t.free_vars().contains(x) {
+                result.push((**t).clone());
+            }
+
+            result.extend(
+                shrink_named(t)
+                    .into_iter()
+                    .map(|t| NamedTerm::lambda(x.clone(), t)),
+            );
+            result
+        },
+        NamedTerm::Apply(t1, t2) => shrink_apply(t1, t2, shrink_named, NamedTerm::apply),
+    }
+}
+
+/// Смалява апликация - до двете ѝ части или чрез смаляване на едната от тях.
+fn shrink_apply<T: Clone>(
+    t1: &T,
+    t2: &T,
+    shrink: fn(&T) -> Vec<T>,
+    apply: fn(T, T) -> T,
+) -> Vec<T> {
+    let mut result = vec![t1.clone(), t2.clone()];
+    result.extend(shrink(t1).into_iter().map(|t| apply(t, t2.clone())));
+    result.extend(shrink(t2).into_iter().map(|t| apply(t1.clone(), t)));
+    result
+}
+
+/// Генерира затворени безименни термове с размер до `g.size()`.
+impl Arbitrary for UnnamedTerm {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let size = below(g, g.size());
+        gen_unnamed(g, 0, size)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(shrink_unnamed(self).into_iter())
+    }
+}
+
+/// Генерира затворени именувани термове с размер до `g.size()`.
+impl Arbitrary for NamedTerm {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let size = below(g, g.size());
+        gen_named(g, &mut Vec::new(), size)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(shrink_named(self).into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests;
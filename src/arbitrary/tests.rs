@@ -0,0 +1,73 @@
+use super::{named_term, unnamed_term};
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use proptest::prelude::*;
+use quickcheck::{quickcheck, Arbitrary};
+
+fn names(free: &[&str]) -> Vec<String> {
+    free.iter().map(|x| x.to_string()).collect()
+}
+
+proptest! {
+    #[test]
+    fn generated_terms_are_well_scoped(
+        unnamed in unnamed_term(2, 8),
+        named in named_term(&["a", "b"], 8),
+    ) {
+        prop_assert!(unnamed.is_closed_in(2));
+
+        let mut free = names(&["a", "b"]);
+        UnnamedTerm::from_named_with(&named, &mut free);
+        prop_assert_eq!(free, names(&["a", "b"]));
+    }
+
+    #[test]
+    fn unnamed_round_trip(term in unnamed_term(3, 10)) {
+        // Затваряме терма, за да не зависи резултатът от реда на свободните
+        // променливи.
+        let closed = UnnamedTerm::lambda(UnnamedTerm::lambda(UnnamedTerm::lambda(term)));
+        let named = NamedTerm::from_unnamed(&closed);
+
+        prop_assert_eq!(UnnamedTerm::from_named(&named).0, closed);
+    }
+
+    #[test]
+    fn named_round_trip(term in named_term(&["a", "b"], 10)) {
+        let mut free = names(&["a", "b"]);
+        let unnamed = UnnamedTerm::from_named_with(&term, &mut free);
+        let named = NamedTerm::from_unnamed(&unnamed);
+
+        prop_assert_eq!(UnnamedTerm::from_named_with(&named, &mut free), unnamed);
+    }
+
+    #[test]
+    fn substitutions_agree(
+        term in named_term(&["a", "b", "c"], 8),
+        subs in named_term(&["a", "b", "c"], 6),
+        var in 0..3usize,
+    ) {
+        let mut free = names(&["a", "b", "c"]);
+
+        let named = term.substitute(&free[var].clone(), &subs);
+        let unnamed = UnnamedTerm::from_named_with(&term, &mut free)
+            .substitute(var, &UnnamedTerm::from_named_with(&subs, &mut free));
+
+        prop_assert_eq!(UnnamedTerm::from_named_with(&named, &mut free), unnamed);
+    }
+}
+
+quickcheck! {
+    fn closed_unnamed_round_trip(term: UnnamedTerm) -> bool {
+        term.is_closed_in(0) && UnnamedTerm::from_named(&NamedTerm::from_unnamed(&term)).0 == term
+    }
+
+    fn closed_named_round_trip(term: NamedTerm) -> bool {
+        let (unnamed, free) = UnnamedTerm::from_named(&term);
+        free.is_empty() && UnnamedTerm::from_named(&NamedTerm::from_unnamed(&unnamed)).0 == unnamed
+    }
+
+    fn shrinking_keeps_terms_closed(unnamed: UnnamedTerm, named: NamedTerm) -> bool {
+        unnamed.shrink().all(|t| t.is_closed_in(0))
+            && named.shrink().all(|t| t.free_vars().is_empty())
+    }
+}
@@ -0,0 +1,452 @@
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use std::convert::TryFrom;
+use std::mem;
+
+/// Номер на възел в `Arena`.
+///
+/// Номерата са валидни само за арената, която ги е върнала, и само до
+/// следващото извикване на `Arena::compact`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct TermId(u32);
+
+impl TermId {
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Възел на безименен терм, чиито деца са в същата арена.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Node {
+    Var(usize),
+    Apply(TermId, TermId),
+    Lambda(TermId),
+}
+
+/// Безименни термове, записани последователно в един `Vec`.
+///
+/// Всеки възел заема 16 байта и не изисква отделна заделена памет, докато
+/// `unnamed::Term` заделя по една кутия за всеки възел. Възлите никога не се
+/// променят, така че субституцията и редукцията преизползват непроменените
+/// подтермове вместо да ги копират. Недостижимите възли се освобождават
+/// с `compact`.
+///
+/// # Пример
+///
+/// ```
+/// use hw::arena::Arena;
+/// use hw::unnamed_term;
+///
+/// let mut arena = Arena::new();
+/// let id = arena.alloc_unnamed(&unnamed_term!((λ λ 1) 2 ((λ 0 0) (λ 0 0))));
+///
+/// let normal_form = arena.normalize(id, 100).unwrap();
+/// assert_eq!(arena.to_unnamed(normal_form), unnamed_term!(2));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Arena {
+    nodes: Vec<Node>,
+}
+
+impl Arena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena { nodes: Vec::with_capacity(capacity) }
+    }
+
+    /// Връща броя на възлите в арената, включително недостижимите.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Връща заетата от арената памет в байтове.
+    pub fn memory(&self) -> usize {
+        self.nodes.capacity() * mem::size_of::<Node>()
+    }
+
+    fn node(&self, id: TermId) -> Node {
+        self.nodes[id.index()]
+    }
+
+    fn push(&mut self, node: Node) -> TermId {
+        let id = u32::try_from(self.nodes.len()).expect("arena is full");
+        self.nodes.push(node);
+        TermId(id)
+    }
+
+    pub fn var(&mut self, n: usize) -> TermId {
+        self.push(Node::Var(n))
+    }
+
+    pub fn apply(&mut self, t1: TermId, t2: TermId) -> TermId {
+        self.push(Node::Apply(t1, t2))
+    }
+
+    pub fn lambda(&mut self, t: TermId) -> TermId {
+        self.push(Node::Lambda(t))
+    }
+
+    /// Записва безименен терм в арената.
+    pub fn alloc_unnamed(&mut self, term: &UnnamedTerm) -> TermId {
+        enum Frame<'a> {
+            Visit(&'a UnnamedTerm),
+            Apply,
+            Lambda,
+        }
+
+        let mut frames = vec![Frame::Visit(term)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(UnnamedTerm::Var(n)) => results.push(self.var(*n)),
+                Frame::Visit(UnnamedTerm::Apply(t1, t2)) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2));
+                    frames.push(Frame::Visit(t1));
+                },
+                Frame::Visit(UnnamedTerm::Lambda(t)) => {
+                    frames.push(Frame::Lambda);
+                    frames.push(Frame::Visit(t));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(self.apply(t1, t2));
+                },
+                Frame::Lambda => {
+                    let t = results.pop().unwrap();
+                    results.push(self.lambda(t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+
+    /// Записва именуван терм в арената.
+    ///
+    /// Както `unnamed::Term::from_named`, връща и контекста от имена на
+    /// свободните променливи.
+    pub fn alloc_named(&mut self, term: &NamedTerm) -> (TermId, Vec<String>) {
+        let (term, names) = UnnamedTerm::from_named(term);
+        (self.alloc_unnamed(&term), names)
+    }
+
+    /// Връща терма с номер `id` като безименен терм.
+    pub fn to_unnamed(&self, id: TermId) -> UnnamedTerm {
+        enum Frame {
+            Visit(TermId),
+            Apply,
+            Lambda,
+        }
+
+        let mut frames = vec![Frame::Visit(id)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(id) => match self.node(id) {
+                    Node::Var(n) => results.push(UnnamedTerm::var(n)),
+                    Node::Apply(t1, t2) => {
+                        frames.push(Frame::Apply);
+                        frames.push(Frame::Visit(t2));
+                        frames.push(Frame::Visit(t1));
+                    },
+                    Node::Lambda(t) => {
+                        frames.push(Frame::Lambda);
+                        frames.push(Frame::Visit(t));
+                    },
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(UnnamedTerm::apply(t1, t2));
+                },
+                Frame::Lambda => {
+                    let t = results.pop().unwrap();
+                    results.push(UnnamedTerm::lambda(t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+
+    /// Връща терма с номер `id` като именуван терм с автоматично генерирани
+    /// имена, както `named::Term::from_unnamed`.
+    pub fn to_named(&self, id: TermId) -> NamedTerm {
+        NamedTerm::from_unnamed(&self.to_unnamed(id))
+    }
+
+    /// Изпълнява субституцията `term[var -> subs]`.
+    ///
+    /// Подтермовете, които не съдържат `var`, не се копират.
+    pub fn substitute(&mut self, id: TermId, var: usize, subs: TermId) -> TermId {
+        // Индексите в `subs` се увеличават с `depth` едва при заместването.
+        self.map_vars(id, |arena, id, x, depth| {
+            if x == var + depth {
+                arena.raise(subs, 0, depth)
+            } else {
+                id
+            }
+        })
+    }
+
+    /// Увеличава с `by` индексите на променливите, които са свободни
+    /// спрямо първите `from` абстракции.
+    fn raise(&mut self, id: TermId, from: usize, by: usize) -> TermId {
+        if by == 0 {
+            return id;
+        }
+
+        self.map_vars(id, |arena, id, x, depth| {
+            if x < from + depth {
+                id
+            } else {
+                arena.var(x + by)
+            }
+        })
+    }
+
+    /// Намалява с единица индексите на променливите, които са свободни
+    /// спрямо първите `from` абстракции.
+    fn lower(&mut self, id: TermId, from: usize) -> TermId {
+        self.map_vars(id, |arena, id, x, depth| {
+            if x < from + depth {
+                id
+            } else {
+                arena.var(x - 1)
+            }
+        })
+    }
+
+    /// Заменя всяка променлива `x` с номер `id`, намираща се под `depth`
+    /// абстракции, с `f(self, id, x, depth)`. Непроменените подтермове не се
+    /// копират.
+    fn map_vars<F>(&mut self, id: TermId, mut f: F) -> TermId
+    where
+        F: FnMut(&mut Self, TermId, usize, usize) -> TermId,
+    {
+        enum Frame {
+            Visit(TermId, usize),
+            Apply(TermId, TermId, TermId),
+            Lambda(TermId, TermId),
+        }
+
+        let mut frames = vec![Frame::Visit(id, 0)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(id, depth) => match self.node(id) {
+                    Node::Var(x) => {
+                        let result = f(self, id, x, depth);
+                        results.push(result);
+                    },
+                    Node::Apply(t1, t2) => {
+                        frames.push(Frame::Apply(id, t1, t2));
+                        frames.push(Frame::Visit(t2, depth));
+                        frames.push(Frame::Visit(t1, depth));
+                    },
+                    Node::Lambda(t) => {
+                        frames.push(Frame::Lambda(id, t));
+                        frames.push(Frame::Visit(t, depth + 1));
+                    },
+                },
+                Frame::Apply(id, t1, t2) => {
+                    let s2 = results.pop().unwrap();
+                    let s1 = results.pop().unwrap();
+                    results.push(self.rebuild_apply(id, (t1, t2), (s1, s2)));
+                },
+                Frame::Lambda(id, t) => {
+                    let s = results.pop().unwrap();
+                    results.push(self.rebuild_lambda(id, t, s));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+
+    fn rebuild_apply(
+        &mut self,
+        id: TermId,
+        old: (TermId, TermId),
+        new: (TermId, TermId),
+    ) -> TermId {
+        if old == new {
+            id
+        } else {
+            self.apply(new.0, new.1)
+        }
+    }
+
+    fn rebuild_lambda(&mut self, id: TermId, old: TermId, new: TermId) -> TermId {
+        if old == new {
+            id
+        } else {
+            self.lambda(new)
+        }
+    }
+
+    /// Свива редекса с номер `id`. Връща `None`, ако терма не е редекс.
+    pub fn contract(&mut self, id: TermId) -> Option<TermId> {
+        match self.node(id) {
+            Node::Apply(t1, arg) => match self.node(t1) {
+                Node::Lambda(body) => {
+                    let arg = self.raise(arg, 0, 1);
+                    let result = self.substitute(body, 0, arg);
+                    Some(self.lower(result, 0))
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Прави една стъпка с нормалната стратегия.
+    pub fn reduce_step(&mut self, id: TermId) -> Option<TermId> {
+        enum Side {
+            Left,
+            Right,
+            Body,
+        }
+
+        // Върховете се обхождат в нормален ред, като за всеки се пази
+        // родителят му, за да може пътят до редекса да се построи наново.
+        let mut visited = Vec::new();
+        let mut stack = vec![(id, None, Side::Body)];
+
+        let redex = loop {
+            let (id, parent, side) = stack.pop()?;
+            let index = visited.len();
+            visited.push((id, parent, side));
+
+            match self.node(id) {
+                Node::Var(_) => {},
+                Node::Apply(t1, t2) => {
+                    if let Node::Lambda(_) = self.node(t1) {
+                        break index;
+                    }
+
+                    stack.push((t2, Some(index), Side::Right));
+                    stack.push((t1, Some(index), Side::Left));
+                },
+                Node::Lambda(t) => stack.push((t, Some(index), Side::Body)),
+            }
+        };
+
+        let mut result = self.contract(visited[redex].0)?;
+        let mut index = redex;
+
+        while let (_, Some(parent), side) = &visited[index] {
+            result = match (self.node(visited[*parent].0), side) {
+                (Node::Apply(_, t2), Side::Left) => self.apply(result, t2),
+                (Node::Apply(t1, _), Side::Right) => self.apply(t1, result),
+                (Node::Lambda(_), Side::Body) => self.lambda(result),
+                _ => unreachable!(),
+            };
+            index = *parent;
+        }
+
+        Some(result)
+    }
+
+    /// Нормализира терма с нормалната стратегия, като прави не повече от
+    /// `fuel` стъпки. Връща `None`, ако стъпките не са стигнали.
+    ///
+    /// Междинните термове остават в арената до следващото извикване на
+    /// `compact`.
+    pub fn normalize(&mut self, id: TermId, fuel: usize) -> Option<TermId> {
+        let mut id = id;
+
+        for _ in 0..fuel {
+            match self.reduce_step(id) {
+                Some(next) => id = next,
+                None => return Some(id),
+            }
+        }
+
+        match self.reduce_step(id) {
+            Some(_) => None,
+            None => Some(id),
+        }
+    }
+
+    /// Премахва всички възли, които не са достижими от `roots`.
+    ///
+    /// Връща новите номера на `roots`, като всички останали номера стават
+    /// невалидни.
+    pub fn compact(&mut self, roots: &[TermId]) -> Vec<TermId> {
+        let mut compacted = Arena::new();
+        let mut moved = vec![None; self.nodes.len()];
+
+        let roots = roots.iter().map(|&root| self.copy(root, &mut compacted, &mut moved)).collect();
+        *self = compacted;
+
+        roots
+    }
+
+    fn copy(&self, id: TermId, target: &mut Arena, moved: &mut [Option<TermId>]) -> TermId {
+        enum Frame {
+            Visit(TermId),
+            Apply(TermId),
+            Lambda(TermId),
+        }
+
+        let mut frames = vec![Frame::Visit(id)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            let (id, result) = match frame {
+                Frame::Visit(id) => {
+                    if let Some(result) = moved[id.index()] {
+                        results.push(result);
+                        continue;
+                    }
+
+                    match self.node(id) {
+                        Node::Var(n) => (id, target.var(n)),
+                        Node::Apply(t1, t2) => {
+                            frames.push(Frame::Apply(id));
+                            frames.push(Frame::Visit(t2));
+                            frames.push(Frame::Visit(t1));
+                            continue;
+                        },
+                        Node::Lambda(t) => {
+                            frames.push(Frame::Lambda(id));
+                            frames.push(Frame::Visit(t));
+                            continue;
+                        },
+                    }
+                },
+                Frame::Apply(id) => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    (id, target.apply(t1, t2))
+                },
+                Frame::Lambda(id) => {
+                    let t = results.pop().unwrap();
+                    (id, target.lambda(t))
+                },
+            };
+
+            moved[id.index()] = Some(result);
+            results.push(result);
+        }
+
+        results.pop().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests;
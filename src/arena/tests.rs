@@ -0,0 +1,133 @@
+use super::Arena;
+use crate::fixtures::{self, tree};
+use crate::unnamed::{Strategy, Term};
+use crate::{term, unnamed_term};
+
+#[test]
+fn conversion() {
+    let mut arena = Arena::new();
+
+    let term = unnamed_term!(λ (λ 1 0) (2 (λ 0)));
+    let id = arena.alloc_unnamed(&term);
+    assert_eq!(arena.to_unnamed(id), term);
+    assert_eq!(arena.len(), 10);
+
+    let named = term!(λ x. y (λ z. x z y) w);
+    let (id, names) = arena.alloc_named(&named);
+    assert_eq!((arena.to_unnamed(id), names.clone()), Term::from_named(&named));
+    assert_eq!(names, vec!["y", "w"]);
+    assert_eq!(arena.to_named(id), term!(λ x. a (λ y. x y a) b));
+}
+
+#[test]
+fn substitute() {
+    let cases = vec![
+        (unnamed_term!(0 1 2), 1, unnamed_term!(λ 0)),
+        (unnamed_term!(λ 0 1 2), 1, unnamed_term!(3 (λ 1))),
+        (unnamed_term!(λ λ 2 (λ 3)), 0, unnamed_term!(0 1)),
+        (unnamed_term!(λ 1 (λ 0)), 5, unnamed_term!(0)),
+    ];
+
+    for (term, var, subs) in cases {
+        let mut arena = Arena::new();
+        let id = arena.alloc_unnamed(&term);
+        let subs_id = arena.alloc_unnamed(&subs);
+
+        let result = arena.substitute(id, var, subs_id);
+        assert_eq!(arena.to_unnamed(result), term.substitute(var, &subs), "{}", term);
+    }
+}
+
+#[test]
+fn substitute_shares_unchanged_subterms() {
+    let mut arena = Arena::new();
+    let id = arena.alloc_unnamed(&unnamed_term!((λ 0 1) (λ λ 0 1) 2));
+    let subs = arena.var(7);
+    let len = arena.len();
+
+    assert_eq!(arena.substitute(id, 5, subs), id);
+    assert_eq!(arena.len(), len);
+
+    arena.substitute(id, 2, subs);
+    assert_eq!(arena.len(), len + 1);
+}
+
+#[test]
+fn large_term() {
+    let term = tree(12);
+    let subs = unnamed_term!(λ 0);
+
+    let mut arena = Arena::new();
+    let id = arena.alloc_unnamed(&term);
+    assert_eq!(arena.len(), (1 << 13) - 1);
+
+    let subs_id = arena.alloc_unnamed(&subs);
+    let result = arena.substitute(id, 0, subs_id);
+    assert_eq!(arena.to_unnamed(result), term.substitute(0, &subs));
+}
+
+#[test]
+fn deep_terms() {
+    const DEPTH: usize = 100_000;
+
+    let lambdas = (0..DEPTH).fold(Term::var(DEPTH), |t, _| Term::lambda(t));
+    let redex = (0..DEPTH).fold(unnamed_term!((λ 0) 0), |t, _| Term::lambda(t));
+
+    let mut arena = Arena::new();
+    let lambdas_id = arena.alloc_unnamed(&lambdas);
+    let redex_id = arena.alloc_unnamed(&redex);
+
+    let subs = arena.alloc_unnamed(&unnamed_term!(λ 1));
+    let result = arena.substitute(lambdas_id, 0, subs);
+    assert_eq!(arena.to_unnamed(result), lambdas.substitute(0, &unnamed_term!(λ 1)));
+
+    let normal_form = arena.normalize(redex_id, 1).unwrap();
+    let expected = (0..DEPTH).fold(Term::var(0), |t, _| Term::lambda(t));
+    assert_eq!(arena.to_unnamed(normal_form), expected);
+
+    let k = arena.alloc_unnamed(&unnamed_term!(λ λ 1));
+    let id = arena.apply(k, lambdas_id);
+    let normal_form = arena.normalize(id, 1).unwrap();
+    assert_eq!(arena.to_unnamed(normal_form), Term::lambda(lambdas.raise(0)));
+
+    let roots = arena.compact(&[result, lambdas_id]);
+    assert_eq!(arena.to_unnamed(roots[1]), lambdas);
+}
+
+#[test]
+fn normalize() {
+    for term in fixtures::sample_terms() {
+        let mut arena = Arena::new();
+        let id = arena.alloc_unnamed(&term);
+
+        assert_eq!(
+            arena.normalize(id, 1000).map(|id| arena.to_unnamed(id)),
+            term.normalize(Strategy::NormalOrder, 1000, false),
+            "{}",
+            term
+        );
+    }
+
+    let mut arena = Arena::new();
+    let omega = arena.alloc_unnamed(&unnamed_term!((λ 0 0) (λ 0 0)));
+    assert_eq!(arena.normalize(omega, 1000), None);
+
+    let id = arena.alloc_unnamed(&unnamed_term!((λ 0) ((λ 0) 1)));
+    assert_eq!(arena.normalize(id, 1), None);
+    assert_eq!(arena.normalize(id, 2).map(|id| arena.to_unnamed(id)), Some(unnamed_term!(1)));
+}
+
+#[test]
+fn compact() {
+    let mut arena = Arena::new();
+    let term = unnamed_term!((λ λ 1 0) (λ 0) ((λ 0) 5));
+    let first = arena.alloc_unnamed(&term);
+    let normal_form = arena.normalize(first, 100).unwrap();
+    let len = arena.len();
+
+    let roots = arena.compact(&[normal_form, normal_form]);
+    assert_eq!(roots[0], roots[1]);
+    assert_eq!(arena.to_unnamed(roots[0]), unnamed_term!(5));
+    assert!(arena.len() < len);
+    assert_eq!(arena.len(), 1);
+}
@@ -0,0 +1,178 @@
+use hw::development::LabelledTerm;
+use hw::named::Term as NamedTerm;
+use hw::unnamed::{Strategy, Term};
+
+use std::io::{stdin, stdout, BufRead, Write};
+
+const HELP: &str = "\
+step [N]            прави една (или N) стъпки с текущата стратегия
+run                 редуцира до нормална форма
+redexes             показва номерата на редексите
+contract N          свива редекса с номер N
+undo                отменя последната стъпка
+strategy [NAME]     показва или сменя стратегията (normal, applicative, name, value)
+print [NOTATION]    принтира терма, като може да смени нотацията (named, debruijn)
+help                показва тази помощ
+quit                изход";
+
+/// Нотация, в която се принтира текущият терм.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Notation {
+    Named,
+    DeBruijn,
+}
+
+/// Състояние на интерактивното редуциране.
+pub struct Debugger {
+    /// Всички термове от началото на редукцията. Последният е текущият.
+    history: Vec<Term>,
+    /// Имената на свободните променливи на началния терм по индекс.
+    names: Vec<String>,
+    strategy: Strategy,
+    notation: Notation,
+    /// Максимален брой стъпки за командата `run`.
+    fuel: usize,
+}
+
+impl Debugger {
+    /// Създава дебъгер за `term`, чиито свободни променливи се принтират с
+    /// имената от `names`.
+    pub fn new(term: Term, names: Vec<String>, fuel: usize) -> Self {
+        Debugger {
+            history: vec![term],
+            names,
+            strategy: Strategy::NormalOrder,
+            notation: Notation::Named,
+            fuel,
+        }
+    }
+
+    fn current(&self) -> &Term {
+        self.history.last().unwrap()
+    }
+
+    /// Принтира текущия терм, предшестван от броя на направените стъпки.
+    fn show(&self) -> String {
+        let term = match self.notation {
+            Notation::Named => {
+                NamedTerm::from_unnamed_with(self.current(), &self.names).to_string()
+            },
+            Notation::DeBruijn => self.current().to_string(),
+        };
+
+        format!("[{}] {}", self.history.len() - 1, term)
+    }
+
+    /// Прави до `n` стъпки с текущата стратегия. Връща броя на направените
+    /// стъпки.
+    fn step(&mut self, n: usize) -> usize {
+        for i in 0..n {
+            match self.current().reduce_step(self.strategy, false) {
+                Some(next) => self.history.push(next),
+                None => return i,
+            }
+        }
+
+        n
+    }
+
+    /// Изпълнява един ред от входа и връща текста, който трябва да се
+    /// изведе, или `None`, ако потребителят е поискал изход.
+    pub fn execute(&mut self, line: &str) -> Option<String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        let number = |i: usize| words.get(i).map(|w| w.parse::<usize>());
+
+        let output = match (words.first().copied(), words.len()) {
+            (None, _) => String::new(),
+            (Some("step"), 1..=2) | (Some("s"), 1..=2) => match number(1).unwrap_or(Ok(1)) {
+                Ok(n) if self.step(n) < n => {
+                    format!("{}\nтермът е в нормална форма за стратегията", self.show())
+                },
+                Ok(_) => self.show(),
+                Err(_) => format!("невалиден брой стъпки '{}'", words[1]),
+            },
+            (Some("run"), 1) => {
+                let fuel = self.fuel;
+                self.step(fuel);
+
+                if self.current().reduce_step(self.strategy, false).is_some() {
+                    format!("{}\nне е достигната нормална форма за {} стъпки", self.show(), fuel)
+                } else {
+                    self.show()
+                }
+            },
+            (Some("redexes"), 1) | (Some("r"), 1) => {
+                let all = (0..self.current().reducts().len()).collect::<Vec<_>>();
+                LabelledTerm::new(self.current(), &all).to_string()
+            },
+            (Some("contract"), 2) | (Some("c"), 2) => match number(1).unwrap() {
+                Ok(n) => match self.current().reducts().into_iter().nth(n) {
+                    Some(next) => {
+                        self.history.push(next);
+                        self.show()
+                    },
+                    None => format!("няма редекс с номер {}", n),
+                },
+                Err(_) => format!("невалиден номер на редекс '{}'", words[1]),
+            },
+            (Some("undo"), 1) | (Some("u"), 1) => {
+                if self.history.len() > 1 {
+                    self.history.pop();
+                    self.show()
+                } else {
+                    "няма стъпка за отмяна".to_string()
+                }
+            },
+            (Some("strategy"), 1) => self.strategy.to_string(),
+            (Some("strategy"), 2) => match words[1].parse() {
+                Ok(strategy) => {
+                    self.strategy = strategy;
+                    self.strategy.to_string()
+                },
+                Err(e) => e,
+            },
+            (Some("print"), 1) | (Some("p"), 1) => self.show(),
+            (Some("print"), 2) | (Some("p"), 2) => {
+                match words[1] {
+                    "named" => self.notation = Notation::Named,
+                    "debruijn" => self.notation = Notation::DeBruijn,
+                    _ => return Some(format!("непозната нотация '{}'", words[1])),
+                }
+                self.show()
+            },
+            (Some("help"), 1) | (Some("h"), 1) => HELP.to_string(),
+            (Some("quit"), 1) | (Some("q"), 1) => return None,
+            (Some(command), _) => format!("непозната команда '{}' (вижте help)", command),
+        };
+
+        Some(output)
+    }
+
+    /// Изпълнява команди от стандартния вход до команда `quit` или до края
+    /// на входа.
+    pub fn run(&mut self) {
+        println!("{}", self.show());
+
+        let stdin = stdin();
+        let mut lines = stdin.lock().lines();
+
+        loop {
+            print!("> ");
+            stdout().flush().unwrap();
+
+            let line = match lines.next() {
+                Some(line) => line.unwrap(),
+                None => break,
+            };
+
+            match self.execute(&line) {
+                Some(output) if output.is_empty() => {},
+                Some(output) => println!("{}", output),
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+use super::Debugger;
+use hw::unnamed_term;
+
+fn debugger() -> Debugger {
+    // (λ x. λ y. y) ((λ z. z) w)
+    Debugger::new(unnamed_term!((λ λ 0) ((λ 0) 0)), Vec::new(), 100)
+}
+
+#[test]
+fn step_and_undo() {
+    let mut debugger = debugger();
+
+    assert_eq!(debugger.execute("step").unwrap(), "[1] λ x. x");
+    assert_eq!(
+        debugger.execute("step").unwrap(),
+        "[1] λ x. x\nтермът е в нормална форма за стратегията"
+    );
+    assert_eq!(debugger.execute("undo").unwrap(), "[0] (λ x. λ y. y) ((λ x. x) a)");
+    assert_eq!(debugger.execute("undo").unwrap(), "няма стъпка за отмяна");
+
+    assert_eq!(debugger.execute("strategy value").unwrap(), "value");
+    assert_eq!(debugger.execute("step 2").unwrap(), "[2] λ x. x");
+    assert_eq!(debugger.execute("u").unwrap(), "[1] (λ x. λ y. y) a");
+}
+
+#[test]
+fn contract_redex() {
+    let mut debugger = debugger();
+
+    assert_eq!(debugger.execute("redexes").unwrap(), "[(λ λ 0) [(λ 0) 0]₁]₀");
+    assert_eq!(debugger.execute("contract 1").unwrap(), "[1] (λ x. λ y. y) a");
+    assert_eq!(debugger.execute("contract 1").unwrap(), "няма редекс с номер 1");
+    assert_eq!(debugger.execute("print debruijn").unwrap(), "[1] (λ λ 0) 0");
+    assert_eq!(debugger.execute("run").unwrap(), "[2] λ 0");
+}
+
+#[test]
+fn errors() {
+    let mut debugger = debugger();
+
+    assert_eq!(debugger.execute(""), Some(String::new()));
+    assert_eq!(debugger.execute("quit"), None);
+    assert_eq!(debugger.execute("jump").unwrap(), "непозната команда 'jump' (вижте help)");
+    assert_eq!(debugger.execute("strategy lazy").unwrap(), "непозната стратегия 'lazy'");
+    assert_eq!(debugger.execute("step x").unwrap(), "невалиден брой стъпки 'x'");
+    assert_eq!(debugger.execute("step 1 2").unwrap(), "непозната команда 'step' (вижте help)");
+    assert_eq!(debugger.execute("s 1 2").unwrap(), "непозната команда 's' (вижте help)");
+    assert_eq!(debugger.execute("print latex").unwrap(), "непозната нотация 'latex'");
+
+    let mut debugger = Debugger::new(unnamed_term!((λ 0 0) (λ 0 0)), Vec::new(), 10);
+    assert_eq!(
+        debugger.execute("run").unwrap(),
+        "[10] (λ x. x x) (λ x. x x)\nне е достигната нормална форма за 10 стъпки"
+    );
+}
+
+#[test]
+fn run_reaching_normal_form_on_last_step() {
+    let mut debugger = Debugger::new(unnamed_term!((λ 0) 0), vec!["z".to_string()], 1);
+    assert_eq!(debugger.execute("run").unwrap(), "[1] z");
+}
+
+#[test]
+fn free_variable_names() {
+    // (λ x. x z) (λ y. y)
+    let mut debugger = Debugger::new(unnamed_term!((λ 0 1) (λ 0)), vec!["z".to_string()], 10);
+
+    assert_eq!(debugger.execute("print").unwrap(), "[0] (λ x. x z) (λ x. x)");
+    assert_eq!(debugger.execute("step").unwrap(), "[1] (λ x. x) z");
+    assert_eq!(debugger.execute("print debruijn").unwrap(), "[1] (λ 0) 0");
+}
@@ -0,0 +1,261 @@
+use crate::unnamed::Term;
+use std::fmt::{self, Display};
+
+/// Терм без имена, в който някои редекси са означени с етикети.
+///
+/// Етикетът е в апликацията на редекса. При свиване на редекс етикетите в
+/// тялото и в аргумента му се запазват, така че всеки остатък на означен
+/// редекс носи етикета на редекса, от който произхожда.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LabelledTerm {
+    Var(usize),
+    Apply(Box<LabelledTerm>, Box<LabelledTerm>, Option<usize>),
+    Lambda(Box<LabelledTerm>),
+}
+
+/// Една стъпка от развитие.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Step {
+    /// Етикетът на свития остатък.
+    pub label: usize,
+    /// Термът след стъпката.
+    pub term: LabelledTerm,
+}
+
+/// Развитие на множество от означени редекси - редица от стъпки, всяка от
+/// които свива остатък на означен редекс.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Development {
+    /// Началният терм с означените редекси.
+    pub start: LabelledTerm,
+    pub steps: Vec<Step>,
+}
+
+impl Development {
+    /// Връща крайния терм на развитието.
+    pub fn result(&self) -> &LabelledTerm {
+        self.steps.last().map_or(&self.start, |step| &step.term)
+    }
+}
+
+/// Извършва пълно развитие на редексите на `term` с номера `marked`.
+///
+/// Редексите се номерират от 0 по позицията си - отляво надясно и отвън
+/// навътре, както в `Term::reducts`. Номерата, които не съответстват на
+/// редекс, се пренебрегват. На всяка стъпка се свива най-левият най-външен
+/// остатък на означен редекс, докато остатъци има. По теоремата за крайните
+/// развития това винаги завършва, а резултатът не зависи от реда на
+/// стъпките.
+///
+/// # Пример
+///
+/// ```
+/// use hw::{development, unnamed_term};
+///
+/// // Означени са и двата редекса на (λ 0 0) ((λ 0) 1)
+/// let development = development::develop(&unnamed_term!((λ 0 0) ((λ 0) 1)), &[0, 1]);
+///
+/// assert_eq!(development.steps.len(), 3);
+/// assert_eq!(development.result().to_term(), unnamed_term!(1 1));
+/// ```
+pub fn develop(term: &Term, marked: &[usize]) -> Development {
+    let start = LabelledTerm::new(term, marked);
+    let mut steps = Vec::new();
+    let mut current = start.clone();
+
+    while let Some((term, label)) = current.contract_labelled() {
+        steps.push(Step {
+            label,
+            term: term.clone(),
+        });
+        current = term;
+    }
+
+    Development { start, steps }
+}
+
+impl LabelledTerm {
+    /// Означава редексите на `term` с номера `marked`, като за етикет на
+    /// всеки редекс се използва номерът му (вж. `develop`).
+    pub fn new(term: &Term, marked: &[usize]) -> Self {
+        Self::label(term, marked, &mut 0)
+    }
+
+    fn label(term: &Term, marked: &[usize], next: &mut usize) -> Self {
+        match term {
+            Term::Var(x) => LabelledTerm::Var(*x),
+            Term::Apply(t1, t2) => {
+                let label = match **t1 {
+                    Term::Lambda(_) => {
+                        *next += 1;
+                        Some(*next - 1).filter(|n| marked.contains(n))
+                    },
+                    _ => None,
+                };
+
+                let t1 = Self::label(t1, marked, next);
+                let t2 = Self::label(t2, marked, next);
+                LabelledTerm::Apply(Box::new(t1), Box::new(t2), label)
+            },
+            Term::Lambda(t) => LabelledTerm::Lambda(Box::new(Self::label(t, marked, next))),
+        }
+    }
+
+    /// Премахва етикетите.
+    pub fn to_term(&self) -> Term {
+        match self {
+            LabelledTerm::Var(x) => Term::var(*x),
+            LabelledTerm::Apply(t1, t2, _) => Term::apply(t1.to_term(), t2.to_term()),
+            LabelledTerm::Lambda(t) => Term::lambda(t.to_term()),
+        }
+    }
+
+    /// Връща етикетите на означените редекси в терма, подредени по
+    /// позицията им. Всеки етикет се среща толкова пъти, колкото остатъка
+    /// има съответният редекс.
+    pub fn residuals(&self) -> Vec<usize> {
+        let mut result = Vec::new();
+        self.collect_residuals(&mut result);
+        result
+    }
+
+    fn collect_residuals(&self, result: &mut Vec<usize>) {
+        match self {
+            LabelledTerm::Var(_) => {},
+            LabelledTerm::Apply(t1, t2, label) => {
+                result.extend(label);
+                t1.collect_residuals(result);
+                t2.collect_residuals(result);
+            },
+            LabelledTerm::Lambda(t) => t.collect_residuals(result),
+        }
+    }
+
+    /// Свива най-левия най-външен означен редекс. Връща получения терм и
+    /// етикета на свития редекс или `None`, ако няма означени редекси.
+    pub fn contract_labelled(&self) -> Option<(LabelledTerm, usize)> {
+        use LabelledTerm::*;
+
+        match self {
+            Var(_) => None,
+            Apply(t1, t2, Some(label)) => match &**t1 {
+                Lambda(body) => {
+                    let result = body.substitute(0, &t2.shift(0, true)).shift(0, false);
+                    Some((result, *label))
+                },
+                _ => None,
+            },
+            Apply(t1, t2, None) => t1
+                .contract_labelled()
+                .map(|(t, label)| (Apply(Box::new(t), t2.clone(), None), label))
+                .or_else(|| {
+                    t2.contract_labelled()
+                        .map(|(t, label)| (Apply(t1.clone(), Box::new(t), None), label))
+                }),
+            Lambda(t) => t.contract_labelled().map(|(t, label)| (Lambda(Box::new(t)), label)),
+        }
+    }
+
+    fn substitute(&self, var: usize, subs: &LabelledTerm) -> LabelledTerm {
+        use LabelledTerm::*;
+
+        match self {
+            Var(x) if *x == var => subs.clone(),
+            Var(x) => Var(*x),
+            Apply(t1, t2, label) => Apply(
+                Box::new(t1.substitute(var, subs)),
+                Box::new(t2.substitute(var, subs)),
+                *label,
+            ),
+            Lambda(t) => Lambda(Box::new(t.substitute(var + 1, &subs.shift(0, true)))),
+        }
+    }
+
+    /// Увеличава (ако `up` е `true`) или намалява с единица индексите на
+    /// променливите, които са свободни спрямо първите `from` абстракции.
+    fn shift(&self, from: usize, up: bool) -> LabelledTerm {
+        use LabelledTerm::*;
+
+        match self {
+            Var(x) if *x < from => Var(*x),
+            Var(x) if up => Var(x.wrapping_add(1)),
+            Var(x) => Var(x.wrapping_sub(1)),
+            Apply(t1, t2, label) => {
+                Apply(Box::new(t1.shift(from, up)), Box::new(t2.shift(from, up)), *label)
+            },
+            Lambda(t) => Lambda(Box::new(t.shift(from + 1, up))),
+        }
+    }
+}
+
+/// Формат за принтиране.
+///
+/// Термът се принтира като `Term`, но всеки означен редекс се огражда в
+/// квадратни скоби с етикета си като индекс, например `[(λ 0) 1]₀`.
+impl Display for LabelledTerm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use LabelledTerm::*;
+
+        match self {
+            Var(x) => write!(f, "{}", x),
+            Apply(t1, t2, label) => {
+                if label.is_some() {
+                    write!(f, "[")?;
+                }
+
+                match **t1 {
+                    Lambda(_) => write!(f, "({})", t1)?,
+                    _ => write!(f, "{}", t1)?,
+                }
+
+                write!(f, " ")?;
+
+                match **t2 {
+                    Var(_) | Apply(_, _, Some(_)) => write!(f, "{}", t2)?,
+                    _ => write!(f, "({})", t2)?,
+                }
+
+                match label {
+                    Some(label) => write!(f, "]{}", subscript(*label)),
+                    None => Ok(()),
+                }
+            },
+            Lambda(t) => write!(f, "λ {}", t),
+        }
+    }
+}
+
+/// Принтира развитието по една стъпка на ред, заедно с останалите остатъци
+/// след всяка стъпка.
+///
+/// ```text
+/// [(λ 0 0) [(λ 0) 1]₁]₀
+/// →₀ [(λ 0) 1]₁ [(λ 0) 1]₁    остатъци: 1, 1
+/// ```
+impl Display for Development {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.start)?;
+
+        for step in &self.steps {
+            write!(f, "\n→{} {}", subscript(step.label), step.term)?;
+
+            let residuals = step.term.residuals();
+            if !residuals.is_empty() {
+                let residuals = residuals.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+                write!(f, "    остатъци: {}", residuals.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn subscript(n: usize) -> String {
+    n.to_string()
+        .chars()
+        .map(|c| std::char::from_u32('₀' as u32 + c.to_digit(10).unwrap()).unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
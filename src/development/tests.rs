@@ -0,0 +1,70 @@
+use super::{develop, LabelledTerm};
+use crate::parallel;
+use crate::unnamed_term;
+
+#[test]
+fn labels() {
+    let term = unnamed_term!((λ 0 0) ((λ 0) 1) ((λ 0) 2));
+    assert_eq!(LabelledTerm::new(&term, &[]).residuals(), vec![]);
+    assert_eq!(LabelledTerm::new(&term, &[0, 2]).residuals(), vec![0, 2]);
+    assert_eq!(LabelledTerm::new(&term, &[1, 5]).residuals(), vec![1]);
+    assert_eq!(LabelledTerm::new(&term, &[0, 1, 2]).to_term(), term);
+}
+
+#[test]
+fn residuals() {
+    let term = unnamed_term!((λ 0 0) ((λ 0) 1));
+
+    let development = develop(&term, &[0, 1]);
+    let residuals = development
+        .steps
+        .iter()
+        .map(|step| (step.label, step.term.residuals()))
+        .collect::<Vec<_>>();
+    assert_eq!(residuals, vec![(0, vec![1, 1]), (1, vec![1]), (1, vec![])]);
+    assert_eq!(development.result().to_term(), unnamed_term!(1 1));
+
+    // Новосъздаденият редекс не се свива.
+    let development = develop(&term, &[1]);
+    assert_eq!(development.steps.len(), 1);
+    assert_eq!(development.result().to_term(), unnamed_term!((λ 0 0) 1));
+
+    // Остатъците в изтрит аргумент изчезват.
+    let development = develop(&unnamed_term!((λ λ 0) ((λ 0) 1)), &[0, 1]);
+    assert_eq!(development.steps.len(), 1);
+    assert_eq!(development.result().to_term(), unnamed_term!(λ 0));
+}
+
+#[test]
+fn complete_development_of_all_redexes() {
+    let terms = vec![
+        unnamed_term!((λ 0 0) ((λ 0) 1)),
+        unnamed_term!((λ λ 1 (1 0)) ((λ 0 0) 2) ((λ 0) 3)),
+        unnamed_term!(λ (λ 0 (λ 0 1)) ((λ λ 1) 0)),
+        unnamed_term!((λ 0 0) (λ 0 0)),
+    ];
+
+    for term in terms {
+        let all = (0..term.reducts().len()).collect::<Vec<_>>();
+        let development = develop(&term, &all);
+
+        assert!(development.result().residuals().is_empty());
+        assert_eq!(development.result().to_term(), parallel::develop(&term), "{}", term);
+    }
+}
+
+#[test]
+fn display() {
+    let term = unnamed_term!((λ 0 0) ((λ 0) 1) 2);
+    assert_eq!(LabelledTerm::new(&term, &[0, 1]).to_string(), "[(λ 0 0) [(λ 0) 1]₁]₀ 2");
+    assert_eq!(LabelledTerm::new(&term, &[]).to_string(), term.to_string());
+
+    let development = develop(&unnamed_term!((λ 0 0) ((λ 0) 1)), &[0, 1]);
+    assert_eq!(
+        development.to_string(),
+        "[(λ 0 0) [(λ 0) 1]₁]₀\n\
+         →₀ [(λ 0) 1]₁ [(λ 0) 1]₁    остатъци: 1, 1\n\
+         →₁ 1 [(λ 0) 1]₁    остатъци: 1\n\
+         →₁ 1 1"
+    );
+}
@@ -0,0 +1,308 @@
+//! Изброяване и преброяване на безименните термове с даден размер.
+//!
+//! Термовете с размер `n` и свободни променливи сред първите `context`
+//! индекса се подреждат така: първо променливите по индекс, после
+//! абстракциите, и накрая апликациите - по размера на функцията, а при равни
+//! размери - лексикографски по (функция, аргумент). Всеки терм има номер
+//! (ранг) в тази наредба, така че `unrank` и `rank` са взаимно обратни.
+//!
+//! Равномерно случайни термове с даден размер се получават, като се избере
+//! равномерно случаен номер и се намери термът с този номер.
+
+use crate::unnamed::Term;
+use num_bigint::{BigUint, RandBigInt};
+use rand::Rng;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Мярка за размер на терм.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Measure {
+    /// Брой върхове - всеки връх, включително променливите, е с размер 1.
+    Nodes,
+    /// Дължина в битове на двоичния запис (BLC) - `00` за абстракция,
+    /// `01` за апликация и `1...10` с `n + 1` единици за индекса `n`.
+    Binary,
+}
+
+impl FromStr for Measure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nodes" => Ok(Measure::Nodes),
+            "binary" => Ok(Measure::Binary),
+            _ => Err(format!("непозната мярка '{}'", s)),
+        }
+    }
+}
+
+impl Measure {
+    fn var_size(self, n: usize) -> usize {
+        match self {
+            Measure::Nodes => 1,
+            Measure::Binary => n + 2,
+        }
+    }
+
+    /// Размерът на абстракция и апликация без децата им.
+    fn node_size(self) -> usize {
+        match self {
+            Measure::Nodes => 1,
+            Measure::Binary => 2,
+        }
+    }
+
+    /// Връща размера на терма.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::enumeration::Measure;
+    /// use hw::unnamed_term;
+    ///
+    /// assert_eq!(Measure::Nodes.size(&unnamed_term!(λ 0 1)), 4);
+    /// assert_eq!(Measure::Binary.size(&unnamed_term!(λ 0 1)), 9);
+    /// ```
+    pub fn size(self, term: &Term) -> usize {
+        match term {
+            Term::Var(n) => self.var_size(*n),
+            Term::Apply(t1, t2) => self.node_size() + self.size(t1) + self.size(t2),
+            Term::Lambda(t) => self.node_size() + self.size(t),
+        }
+    }
+}
+
+/// Брояч на термове по размер и контекст, който запомня вече пресметнатите
+/// бройки.
+#[derive(Debug, Clone)]
+pub struct Counter {
+    measure: Measure,
+    counts: HashMap<(usize, usize), BigUint>,
+}
+
+impl Counter {
+    pub fn new(measure: Measure) -> Self {
+        Counter {
+            measure,
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn measure(&self) -> Measure {
+        self.measure
+    }
+
+    /// Връща броя на термовете с размер `size`, чиито свободни променливи
+    /// са с индекси по-малки от `context`. При `context = 0` това са
+    /// затворените термове.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::enumeration::{Counter, Measure};
+    ///
+    /// let mut counter = Counter::new(Measure::Nodes);
+    /// let counts = (1..=6).map(|n| counter.count(n, 0).to_string()).collect::<Vec<_>>();
+    /// assert_eq!(counts, vec!["0", "1", "2", "4", "13", "42"]);
+    /// ```
+    pub fn count(&mut self, size: usize, context: usize) -> BigUint {
+        if let Some(count) = self.counts.get(&(size, context)) {
+            return count.clone();
+        }
+
+        let mut count = BigUint::from(self.vars(size, context));
+
+        let node = self.measure.node_size();
+        if size > node {
+            let rest = size - node;
+
+            count += self.count(rest, context + 1);
+            for left in 1..rest {
+                count += self.count(left, context) * self.count(rest - left, context);
+            }
+        }
+
+        self.counts.insert((size, context), count.clone());
+        count
+    }
+
+    /// Броят на променливите с размер `size` в контекст `context`.
+    fn vars(&self, size: usize, context: usize) -> usize {
+        match self.measure {
+            Measure::Nodes if size == 1 => context,
+            Measure::Nodes => 0,
+            Measure::Binary if size >= 2 && size - 2 < context => 1,
+            Measure::Binary => 0,
+        }
+    }
+
+    /// Връща терма с номер `rank` сред термовете с размер `size` в контекст
+    /// `context`, или `None`, ако `rank` не е по-малък от броя им.
+    pub fn unrank(&mut self, size: usize, context: usize, rank: &BigUint) -> Option<Term> {
+        if *rank >= self.count(size, context) {
+            return None;
+        }
+
+        let mut rank = rank.clone();
+
+        let vars = BigUint::from(self.vars(size, context));
+        if rank < vars {
+            return Some(match self.measure {
+                Measure::Nodes => Term::var(usize::try_from(&rank).unwrap()),
+                Measure::Binary => Term::var(size - 2),
+            });
+        }
+        rank -= vars;
+
+        let rest = size - self.measure.node_size();
+
+        let lambdas = self.count(rest, context + 1);
+        if rank < lambdas {
+            return Some(Term::lambda(self.unrank(rest, context + 1, &rank)?));
+        }
+        rank -= lambdas;
+
+        for left in 1..rest {
+            let right = self.count(rest - left, context);
+            let applications = self.count(left, context) * &right;
+
+            if rank < applications {
+                let t1 = self.unrank(left, context, &(&rank / &right))?;
+                let t2 = self.unrank(rest - left, context, &(&rank % &right))?;
+                return Some(Term::apply(t1, t2));
+            }
+            rank -= applications;
+        }
+
+        unreachable!("rank is smaller than the count")
+    }
+
+    /// Връща номера на терма сред термовете със същия размер в контекст
+    /// `context`, или `None`, ако терма има свободна променлива с индекс
+    /// поне `context`. Обратна операция на `unrank`.
+    pub fn rank(&mut self, term: &Term, context: usize) -> Option<BigUint> {
+        let size = self.measure.size(term);
+
+        match term {
+            Term::Var(n) if *n >= context => None,
+            Term::Var(n) => match self.measure {
+                Measure::Nodes => Some(BigUint::from(*n)),
+                Measure::Binary => Some(BigUint::default()),
+            },
+            Term::Lambda(t) => {
+                let vars = BigUint::from(self.vars(size, context));
+                Some(vars + self.rank(t, context + 1)?)
+            },
+            Term::Apply(t1, t2) => {
+                let rest = size - self.measure.node_size();
+                let mut rank = BigUint::from(self.vars(size, context));
+                rank += self.count(rest, context + 1);
+
+                let left = self.measure.size(t1);
+                for smaller in 1..left {
+                    rank += self.count(smaller, context) * self.count(rest - smaller, context);
+                }
+
+                let right = self.count(rest - left, context);
+                Some(rank + self.rank(t1, context)? * right + self.rank(t2, context)?)
+            },
+        }
+    }
+
+    /// Връща равномерно случаен терм с размер `size` в контекст `context`,
+    /// или `None`, ако няма такива термове.
+    pub fn sample<R: Rng + ?Sized>(
+        &mut self,
+        size: usize,
+        context: usize,
+        rng: &mut R,
+    ) -> Option<Term> {
+        let count = self.count(size, context);
+        if count == BigUint::default() {
+            return None;
+        }
+
+        let rank = rng.gen_biguint_below(&count);
+        self.unrank(size, context, &rank)
+    }
+}
+
+/// Връща равномерно случаен затворен терм с размер `size` в мярката
+/// `measure`, или `None`, ако няма затворени термове с такъв размер.
+///
+/// # Пример
+///
+/// ```
+/// use hw::enumeration::{random, Measure};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = StdRng::seed_from_u64(42);
+/// let term = random(20, Measure::Nodes, &mut rng).unwrap();
+///
+/// assert_eq!(Measure::Nodes.size(&term), 20);
+/// assert_eq!(random(20, Measure::Nodes, &mut StdRng::seed_from_u64(42)), Some(term));
+/// assert_eq!(random(5, Measure::Binary, &mut rng), None);
+/// ```
+pub fn random<R: Rng + ?Sized>(size: usize, measure: Measure, rng: &mut R) -> Option<Term> {
+    Counter::new(measure).sample(size, 0, rng)
+}
+
+/// Връща броя на термовете с размер `size` в контекст `context`. Виж
+/// `Counter::count`.
+pub fn count(size: usize, measure: Measure, context: usize) -> BigUint {
+    Counter::new(measure).count(size, context)
+}
+
+/// Итератор по всички термове с даден размер и контекст.
+#[derive(Debug, Clone)]
+pub struct Terms {
+    counter: Counter,
+    size: usize,
+    context: usize,
+    next: BigUint,
+}
+
+impl Iterator for Terms {
+    type Item = Term;
+
+    fn next(&mut self) -> Option<Term> {
+        let term = self.counter.unrank(self.size, self.context, &self.next)?;
+        self.next += 1u32;
+        Some(term)
+    }
+}
+
+/// Връща итератор по всички термове с размер `size` в мярката `measure`,
+/// чиито свободни променливи са с индекси по-малки от `context`, в реда на
+/// номерата им. При `context = 0` се изброяват само затворените термове.
+///
+/// # Пример
+///
+/// ```
+/// use hw::enumeration::{terms, Measure};
+/// use hw::unnamed_term;
+///
+/// assert_eq!(
+///     terms(4, Measure::Nodes, 0).collect::<Vec<_>>(),
+///     vec![
+///         unnamed_term!(λ λ λ 0),
+///         unnamed_term!(λ λ λ 1),
+///         unnamed_term!(λ λ λ 2),
+///         unnamed_term!(λ 0 0),
+///     ]
+/// );
+/// ```
+pub fn terms(size: usize, measure: Measure, context: usize) -> Terms {
+    Terms {
+        counter: Counter::new(measure),
+        size,
+        context,
+        next: BigUint::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests;
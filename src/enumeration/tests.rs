@@ -0,0 +1,125 @@
+use super::{count, random, terms, Counter, Measure};
+use crate::unnamed_term;
+use num_bigint::BigUint;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+
+#[test]
+fn counting_sequences() {
+    let nodes = (1..=10).map(|n| count(n, Measure::Nodes, 0)).collect::<Vec<_>>();
+    let expected: Vec<BigUint> = vec![0u32, 1, 2, 4, 13, 42, 139, 506, 1915, 7558]
+        .into_iter()
+        .map(BigUint::from)
+        .collect();
+    assert_eq!(nodes, expected);
+
+    // Броят на затворените термове с двоичен запис с дължина n.
+    let binary = (0..=16).map(|n| count(n, Measure::Binary, 0)).collect::<Vec<_>>();
+    let expected: Vec<BigUint> = vec![0u32, 0, 0, 0, 1, 0, 1, 1, 2, 1, 6, 5, 13, 14, 37, 44, 101]
+        .into_iter()
+        .map(BigUint::from)
+        .collect();
+    assert_eq!(binary, expected);
+}
+
+#[test]
+fn big_counts() {
+    let count = count(100, Measure::Nodes, 0);
+    assert!(count > BigUint::from(u128::MAX));
+}
+
+#[test]
+fn enumeration_agrees_with_count() {
+    for &(measure, max_size) in &[(Measure::Nodes, 9), (Measure::Binary, 20)] {
+        for context in 0..3 {
+            for size in 0..=max_size {
+                let all = terms(size, measure, context).collect::<Vec<_>>();
+                assert_eq!(BigUint::from(all.len()), count(size, measure, context));
+
+                let distinct = all.iter().collect::<HashSet<_>>();
+                assert_eq!(distinct.len(), all.len());
+
+                for term in &all {
+                    assert_eq!(measure.size(term), size, "{}", term);
+                    assert!(term.is_closed_in(context), "{}", term);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn rank_and_unrank() {
+    for &measure in &[Measure::Nodes, Measure::Binary] {
+        let mut counter = Counter::new(measure);
+
+        for (index, term) in terms(8, measure, 1).enumerate() {
+            assert_eq!(counter.rank(&term, 1), Some(BigUint::from(index)), "{}", term);
+        }
+
+        let total = counter.count(8, 1);
+        assert_eq!(counter.unrank(8, 1, &total), None);
+    }
+
+    let mut counter = Counter::new(Measure::Nodes);
+    assert_eq!(counter.rank(&unnamed_term!(λ 1), 0), None);
+    assert_eq!(counter.rank(&unnamed_term!(λ 1), 1), Some(BigUint::from(1u32)));
+}
+
+#[test]
+fn random_terms_have_the_right_size() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for &measure in &[Measure::Nodes, Measure::Binary] {
+        let mut counter = Counter::new(measure);
+
+        for size in 0..40 {
+            match counter.sample(size, 0, &mut rng) {
+                Some(term) => {
+                    assert_eq!(measure.size(&term), size);
+                    assert!(term.is_closed_in(0), "{}", term);
+                },
+                None => assert_eq!(counter.count(size, 0), BigUint::default()),
+            }
+        }
+
+        let term = counter.sample(15, 2, &mut rng).unwrap();
+        assert!(term.is_closed_in(2), "{}", term);
+    }
+}
+
+#[test]
+fn random_terms_are_reproducible() {
+    let sample = |seed| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..10).map(|_| random(30, Measure::Nodes, &mut rng)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(sample(7), sample(7));
+    assert_ne!(sample(7), sample(8));
+}
+
+#[test]
+fn random_terms_are_uniform() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut counter = Counter::new(Measure::Nodes);
+
+    // Има 13 затворени терма с 5 върха.
+    let mut frequencies = HashMap::new();
+    for _ in 0..13_000 {
+        *frequencies.entry(counter.sample(5, 0, &mut rng).unwrap()).or_insert(0) += 1;
+    }
+
+    assert_eq!(frequencies.len(), 13);
+    for (term, &frequency) in &frequencies {
+        assert!(800 < frequency && frequency < 1200, "{}: {}", term, frequency);
+    }
+}
+
+#[test]
+fn measure_from_str() {
+    assert_eq!("nodes".parse::<Measure>(), Ok(Measure::Nodes));
+    assert_eq!("binary".parse::<Measure>(), Ok(Measure::Binary));
+    assert_eq!("depth".parse::<Measure>(), Err("непозната мярка 'depth'".to_string()));
+}
@@ -0,0 +1,108 @@
+//! Примерни термове - числа на Чърч, факториел и балансирани дървета от
+//! апликации. Използват се от тестовете и бенчмарковете.
+//!
+//! # Пример
+//!
+//! ```
+//! use hw::fixtures::{church, factorial};
+//! use hw::nbe;
+//!
+//! assert_eq!(nbe::normalize(&factorial(3), 1000), Some(church(6)));
+//! ```
+
+use crate::lambda::LambdaTerm;
+use crate::named::Term as NamedTerm;
+use crate::term;
+use crate::unnamed::Term;
+#[cfg(test)]
+use crate::unnamed_term;
+
+/// Числото на Чърч `n`, построено по един и същи начин за всяко
+/// представяне.
+pub fn church_in<T: LambdaTerm>(n: usize, f: (T::Binder, T::Var), x: (T::Binder, T::Var)) -> T
+where
+    T::Var: Clone,
+{
+    let body = (0..n).fold(T::var(x.1), |t, _| T::apply(T::var(f.1.clone()), t));
+    T::lambda(f.0, T::lambda(x.0, body))
+}
+
+/// Числото на Чърч `n` като безименен терм.
+pub fn church(n: usize) -> Term {
+    church_in(n, ((), 1), ((), 0))
+}
+
+/// `fact n` с числа на Чърч, изразено чрез наредени двойки `(k, k!)`.
+pub fn factorial(n: usize) -> Term {
+    fn define(name: &str, value: NamedTerm, body: NamedTerm) -> NamedTerm {
+        NamedTerm::apply(NamedTerm::lambda(name, body), value)
+    }
+
+    let n = NamedTerm::from_unnamed(&church(n));
+    let term = define(
+        "pair",
+        term!(λ a. λ b. λ s. s a b),
+        define(
+            "succ",
+            term!(λ n. λ f. λ x. f (n f x)),
+            define(
+                "mult",
+                term!(λ m. λ n. λ f. m (n f)),
+                define(
+                    "step",
+                    term!(λ p. p (λ k. λ r. pair (succ k) (mult (succ k) r))),
+                    NamedTerm::apply(
+                        NamedTerm::apply(
+                            NamedTerm::apply(n, term!(step)),
+                            term!(pair (λ f. λ x. x) (λ f. λ x. f x)),
+                        ),
+                        term!(λ k. λ r. r),
+                    ),
+                ),
+            ),
+        ),
+    );
+
+    Term::from_named(&term).0
+}
+
+/// Балансирано дърво от апликации с дълбочина `depth`, в чиито листа се
+/// редуват свободните променливи 0 и 1.
+pub fn tree(depth: usize) -> Term {
+    fn subtree(depth: usize, leaf: usize) -> Term {
+        if depth == 0 {
+            Term::var(leaf % 2)
+        } else {
+            Term::apply(subtree(depth - 1, 2 * leaf), subtree(depth - 1, 2 * leaf + 1))
+        }
+    }
+
+    subtree(depth, 0)
+}
+
+/// Термове, върху които тестовете сравняват различните начини за оценяване
+/// и нормализиране с `unnamed::Term::normalize`.
+///
+/// Сред тях има свободни променливи, редекси под абстракция, разходящи се
+/// аргументи, които се изтриват, и споделени аргументи.
+#[cfg(test)]
+pub(crate) fn sample_terms() -> Vec<Term> {
+    vec![
+        unnamed_term!(0),
+        unnamed_term!(0 1 2),
+        unnamed_term!(λ (λ 0) 0),
+        unnamed_term!((λ 0) (λ 0)),
+        unnamed_term!((λ λ 1) 3 ((λ 0) 4)),
+        unnamed_term!((λ λ 1) 3 ((λ 0 0) (λ 0 0))),
+        unnamed_term!((λ λ 1 0 2) (λ 0) 5),
+        unnamed_term!((λ λ λ 2 0 1) (λ λ 0 1) 4 ((λ 0) 5)),
+        unnamed_term!((λ 0 (λ 1 (λ 0))) (λ 0)),
+        unnamed_term!(λ (λ λ 1 2 0) (0 1)),
+        unnamed_term!(0 ((λ 0) 1) ((λ 0) 2)),
+        unnamed_term!((λ λ 1 (1 0)) (λ λ 1 (1 0)) (λ 3 0)),
+        unnamed_term!((λ 0 0 0) ((λ λ 1) ((λ 0) (λ 0)))),
+        unnamed_term!((λ 0 0) (λ λ 1 (1 0))),
+        unnamed_term!((λ 0 (0 1)) (λ λ 0 1 1)),
+        unnamed_term!((λ 0 (λ λ 0)) (λ 0 ((λ 0 0) (λ 0 0)))),
+    ]
+}
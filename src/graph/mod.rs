@@ -0,0 +1,258 @@
+use crate::unnamed::{Strategy, Term};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Оценява `term` до слаба глава нормална форма със стратегията извикване
+/// по нужда, като прави не повече от `fuel` β-стъпки.
+///
+/// Термът се превръща в граф, в който аргументите на β-редексите не се
+/// копират, а се споделят. Когато споделен аргумент бъде оценен, възелът му
+/// се обновява на място, така че всеки аргумент се оценява най-много веднъж.
+///
+/// Връща резултата, прочетен обратно като терм, и броя на направените
+/// β-стъпки, или `None`, ако стъпките не са стигнали. Резултатът може да се
+/// различава от този при извикване по име, защото оценените споделени
+/// аргументи се прочитат в оценения си вид, но двата терма са
+/// β-конвертируеми.
+pub fn evaluate(term: &Term, fuel: usize) -> Option<(Term, usize)> {
+    let mut graph = Graph::from_term(term);
+    graph.fuel = fuel;
+
+    let root = graph.root.clone();
+    graph.whnf(&root)?;
+
+    Some((graph.to_term(), graph.steps))
+}
+
+/// Връща броя β-стъпки, които оценяването по нужда спестява спрямо
+/// оценяването по име (`Strategy::CallByName`) на `term` до слаба глава
+/// нормална форма.
+///
+/// Връща `None`, ако за някое от двете оценявания не стигат `fuel` стъпки.
+///
+/// # Пример
+///
+/// ```
+/// use hw::{graph, unnamed_term};
+///
+/// // (λ x. x x) ((λ y. y) (λ z. z))
+/// let term = unnamed_term!((λ 0 0) ((λ 0) (λ 0)));
+/// assert_eq!(graph::saved_steps(&term, 100), Some(1));
+/// ```
+pub fn saved_steps(term: &Term, fuel: usize) -> Option<usize> {
+    let (_, need_steps) = evaluate(term, fuel)?;
+
+    let mut name_steps = 0;
+    let mut current = term.clone();
+    while let Some(next) = current.reduce_step(Strategy::CallByName, false) {
+        if name_steps == fuel {
+            return None;
+        }
+
+        current = next;
+        name_steps += 1;
+    }
+
+    Some(name_steps.saturating_sub(need_steps))
+}
+
+type NodeRef = Rc<RefCell<Node>>;
+
+/// Възел на графа.
+///
+/// Свързаните променливи сочат абстракцията си чрез уникален идентификатор,
+/// така че споделените подграфи не се нуждаят от преномериране на индексите.
+#[derive(Clone)]
+enum Node {
+    /// Свободна променлива - индекс спрямо контекста на целия терм.
+    Free(usize),
+    /// Свързана променлива на абстракцията с даден идентификатор.
+    Bound(usize),
+    Apply(NodeRef, NodeRef),
+    Lambda(usize, NodeRef),
+    /// Препратка към резултата от редукцията на възела.
+    Indirection(NodeRef),
+}
+
+struct Graph {
+    root: NodeRef,
+    next_id: usize,
+    steps: usize,
+    fuel: usize,
+}
+
+impl Graph {
+    fn from_term(term: &Term) -> Self {
+        fn build(term: &Term, binders: &mut Vec<usize>, next_id: &mut usize) -> NodeRef {
+            let node = match term {
+                &Term::Var(i) if i < binders.len() => Node::Bound(binders[binders.len() - i - 1]),
+                &Term::Var(i) => Node::Free(i - binders.len()),
+                Term::Apply(t1, t2) => {
+                    Node::Apply(build(t1, binders, next_id), build(t2, binders, next_id))
+                },
+                Term::Lambda(t) => {
+                    let id = *next_id;
+                    *next_id += 1;
+
+                    binders.push(id);
+                    let body = build(t, binders, next_id);
+                    binders.pop();
+
+                    Node::Lambda(id, body)
+                },
+            };
+
+            Rc::new(RefCell::new(node))
+        }
+
+        let mut next_id = 0;
+        let root = build(term, &mut vec![], &mut next_id);
+
+        Graph {
+            root,
+            next_id,
+            steps: 0,
+            fuel: 0,
+        }
+    }
+
+    /// Оценява възела до слаба глава нормална форма, като го обновява на
+    /// място. Връща `None`, ако стъпките не са стигнали.
+    ///
+    /// Апликациите по гръбнака от възела до главата му се пазят в явен стек,
+    /// така че всяка β-стъпка е една итерация на цикъла.
+    fn whnf(&mut self, node: &NodeRef) -> Option<()> {
+        let mut spine = Vec::new();
+        let mut current = resolve(node);
+
+        loop {
+            let node = current.borrow().clone();
+
+            match node {
+                Node::Apply(fun, _) => {
+                    spine.push(current);
+                    current = resolve(&fun);
+                },
+                Node::Lambda(id, body) if !spine.is_empty() => {
+                    if self.steps == self.fuel {
+                        return None;
+                    }
+                    self.steps += 1;
+
+                    let redex = spine.pop().unwrap();
+                    let arg = match &*redex.borrow() {
+                        Node::Apply(_, arg) => arg.clone(),
+                        _ => unreachable!(),
+                    };
+
+                    let result = self.instantiate(&body, id, &arg).unwrap_or(body);
+                    *redex.borrow_mut() = Node::Indirection(result.clone());
+                    current = resolve(&result);
+                },
+                _ => return Some(()),
+            }
+        }
+    }
+
+    /// Копира тялото на абстракцията с идентификатор `id`, като замества
+    /// свързаната ѝ променлива с `arg`.
+    ///
+    /// Подграфите, в които променливата не се среща свободно, не се копират,
+    /// а се споделят - за тях се връща `None`. Копираните абстракции получават
+    /// нови идентификатори.
+    fn instantiate(&mut self, node: &NodeRef, id: usize, arg: &NodeRef) -> Option<NodeRef> {
+        let current = node.borrow().clone();
+
+        let copy = match current {
+            Node::Free(_) => return None,
+            Node::Bound(x) if x == id => return Some(arg.clone()),
+            Node::Bound(_) => return None,
+            Node::Indirection(target) => return self.instantiate(&target, id, arg),
+            Node::Apply(t1, t2) => {
+                let new_t1 = self.instantiate(&t1, id, arg);
+                let new_t2 = self.instantiate(&t2, id, arg);
+
+                if new_t1.is_none() && new_t2.is_none() {
+                    return None;
+                }
+
+                Node::Apply(new_t1.unwrap_or(t1), new_t2.unwrap_or(t2))
+            },
+            Node::Lambda(x, _) if x == id => return None,
+            Node::Lambda(x, body) => {
+                let new_body = self.instantiate(&body, id, arg)?;
+
+                let new_id = self.next_id;
+                self.next_id += 1;
+                let var = Rc::new(RefCell::new(Node::Bound(new_id)));
+                let new_body = self.instantiate(&new_body, x, &var).unwrap_or(new_body);
+
+                Node::Lambda(new_id, new_body)
+            },
+        };
+
+        Some(Rc::new(RefCell::new(copy)))
+    }
+
+    /// Прочита графа обратно като терм, като разгъща споделените възли.
+    fn to_term(&self) -> Term {
+        fn read(node: &NodeRef, binders: &mut Vec<usize>) -> Term {
+            match &*node.borrow() {
+                Node::Free(i) => Term::var(i + binders.len()),
+                Node::Bound(id) => {
+                    let index = binders.iter().rev().position(|x| x == id).unwrap();
+                    Term::var(index)
+                },
+                Node::Apply(t1, t2) => Term::apply(read(t1, binders), read(t2, binders)),
+                Node::Lambda(id, body) => {
+                    binders.push(*id);
+                    let body = read(body, binders);
+                    binders.pop();
+
+                    Term::lambda(body)
+                },
+                Node::Indirection(target) => read(target, binders),
+            }
+        }
+
+        read(&self.root, &mut vec![])
+    }
+}
+
+/// Графът може да съдържа дълги вериги от препратки (по една за всяка
+/// β-стъпка), затова възлите се освобождават с явен стек.
+impl Drop for Graph {
+    fn drop(&mut self) {
+        let root = Rc::new(RefCell::new(Node::Free(0)));
+        let mut stack = vec![std::mem::replace(&mut self.root, root)];
+
+        while let Some(node) = stack.pop() {
+            if let Ok(node) = Rc::try_unwrap(node) {
+                match node.into_inner() {
+                    Node::Free(_) | Node::Bound(_) => {},
+                    Node::Apply(t1, t2) => stack.extend(vec![t1, t2]),
+                    Node::Lambda(_, body) | Node::Indirection(body) => stack.push(body),
+                }
+            }
+        }
+    }
+}
+
+/// Следва препратките, докато стигне до възел, който не е препратка.
+fn resolve(node: &NodeRef) -> NodeRef {
+    let mut node = node.clone();
+
+    loop {
+        let target = match &*node.borrow() {
+            Node::Indirection(target) => target.clone(),
+            _ => break,
+        };
+
+        node = target;
+    }
+
+    node
+}
+
+#[cfg(test)]
+mod tests;
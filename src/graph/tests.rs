@@ -0,0 +1,57 @@
+use super::*;
+use crate::fixtures;
+use crate::unnamed_term;
+
+#[test]
+fn evaluate_to_whnf() {
+    assert_eq!(evaluate(&unnamed_term!(0 1), 0), Some((unnamed_term!(0 1), 0)));
+    assert_eq!(evaluate(&unnamed_term!(λ (λ 0) 0), 0), Some((unnamed_term!(λ (λ 0) 0), 0)));
+    assert_eq!(evaluate(&unnamed_term!((λ 0) 1), 1), Some((unnamed_term!(1), 1)));
+    assert_eq!(evaluate(&unnamed_term!((λ 0) 1), 0), None);
+    assert_eq!(evaluate(&unnamed_term!((λ λ 1) 2), 1), Some((unnamed_term!(λ 3), 1)));
+    assert_eq!(
+        evaluate(&unnamed_term!((λ λ 1 0) (λ 0 1)), 1),
+        Some((unnamed_term!(λ (λ 0 2) 0), 1))
+    );
+
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+    assert_eq!(evaluate(&omega, 100), None);
+    assert_eq!(evaluate(&omega, 1_000_000), None);
+    assert_eq!(
+        evaluate(&Term::apply(unnamed_term!(λ λ 0), omega), 100),
+        Some((unnamed_term!(λ 0), 1))
+    );
+}
+
+#[test]
+fn shares_arguments() {
+    // (λ x. x x) ((λ y. y) (λ z. z))
+    let term = unnamed_term!((λ 0 0) ((λ 0) (λ 0)));
+    assert_eq!(evaluate(&term, 100), Some((unnamed_term!(λ 0), 3)));
+    assert_eq!(saved_steps(&term, 100), Some(1));
+
+    // (λ x. x (x (x 0))) ((λ y. y) (λ z. z))
+    let term = unnamed_term!((λ 0 (0 (0 1))) ((λ 0) (λ 0)));
+    assert_eq!(evaluate(&term, 100), Some((unnamed_term!(0), 5)));
+    assert_eq!(saved_steps(&term, 100), Some(2));
+
+    // Оцененият аргумент остава споделен и под абстракция.
+    let term = unnamed_term!((λ 0 (λ 1)) ((λ 0) (λ 0)));
+    assert_eq!(evaluate(&term, 100), Some((unnamed_term!(λ λ 0), 3)));
+}
+
+#[test]
+fn agrees_with_call_by_name() {
+    for term in fixtures::sample_terms() {
+        let (result, _) = evaluate(&term, 1000).unwrap();
+        let expected = term.normalize(Strategy::CallByName, 1000, false).unwrap();
+
+        assert_eq!(
+            result.normalize(Strategy::NormalOrder, 1000, false),
+            expected.normalize(Strategy::NormalOrder, 1000, false),
+            "{}",
+            term
+        );
+        assert!(saved_steps(&term, 1000).is_some());
+    }
+}
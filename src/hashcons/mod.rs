@@ -0,0 +1,386 @@
+use crate::unnamed::Term;
+use std::collections::HashMap;
+
+/// Номер на терм в `Store`. Два номера от едно и също хранилище са равни
+/// точно когато термовете са структурно равни.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct TermId(usize);
+
+/// Възел на терм, чиито деца са вече в хранилището.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum Node {
+    Var(usize),
+    Apply(TermId, TermId),
+    Lambda(TermId),
+}
+
+/// Хранилище на безименни термове с hash consing - всеки терм се пази
+/// веднъж и структурно равните термове имат един и същ номер.
+///
+/// Нормалните форми на нормализираните термове и на подтермовете им се
+/// запомнят, така че повторното нормализиране на вече срещнат терм не
+/// прави β-стъпки.
+///
+/// # Пример
+///
+/// ```
+/// use hw::hashcons::Store;
+/// use hw::unnamed_term;
+///
+/// let mut store = Store::new();
+/// let id = store.intern(&unnamed_term!((λ 0 0) (λ 0)));
+///
+/// assert_eq!(id, store.intern(&unnamed_term!((λ 0 0) (λ 0))));
+///
+/// let normal_form = store.normalize(id, 100).unwrap();
+/// assert_eq!(store.to_term(normal_form), unnamed_term!(λ 0));
+/// assert_eq!(store.beta_steps(), 2);
+///
+/// store.normalize(id, 100);
+/// assert_eq!(store.beta_steps(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct Store {
+    nodes: Vec<Node>,
+    /// За всеки възел - броят на свободните му променливи, т.е. най-големият
+    /// индекс на свободна променлива плюс едно.
+    free: Vec<usize>,
+    ids: HashMap<Node, TermId>,
+    normal_forms: HashMap<TermId, TermId>,
+    beta_steps: usize,
+}
+
+impl Store {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Връща броя на различните термове в хранилището.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Връща общия брой β-стъпки, направени от `normalize`.
+    pub fn beta_steps(&self) -> usize {
+        self.beta_steps
+    }
+
+    fn node(&self, id: TermId) -> Node {
+        self.nodes[id.0]
+    }
+
+    fn insert(&mut self, node: Node) -> TermId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let free = match node {
+            Node::Var(n) => n + 1,
+            Node::Apply(t1, t2) => self.free[t1.0].max(self.free[t2.0]),
+            Node::Lambda(t) => self.free[t.0].saturating_sub(1),
+        };
+
+        let id = TermId(self.nodes.len());
+        self.nodes.push(node);
+        self.free.push(free);
+        self.ids.insert(node, id);
+
+        id
+    }
+
+    pub fn var(&mut self, n: usize) -> TermId {
+        self.insert(Node::Var(n))
+    }
+
+    pub fn apply(&mut self, t1: TermId, t2: TermId) -> TermId {
+        self.insert(Node::Apply(t1, t2))
+    }
+
+    pub fn lambda(&mut self, t: TermId) -> TermId {
+        self.insert(Node::Lambda(t))
+    }
+
+    /// Добавя терм в хранилището и връща номера му.
+    pub fn intern(&mut self, term: &Term) -> TermId {
+        enum Frame<'a> {
+            Visit(&'a Term),
+            Apply,
+            Lambda,
+        }
+
+        let mut frames = vec![Frame::Visit(term)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(Term::Var(n)) => results.push(self.var(*n)),
+                Frame::Visit(Term::Apply(t1, t2)) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2));
+                    frames.push(Frame::Visit(t1));
+                },
+                Frame::Visit(Term::Lambda(t)) => {
+                    frames.push(Frame::Lambda);
+                    frames.push(Frame::Visit(t));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(self.apply(t1, t2));
+                },
+                Frame::Lambda => {
+                    let t = results.pop().unwrap();
+                    results.push(self.lambda(t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+
+    /// Връща терма с номер `id`.
+    pub fn to_term(&self, id: TermId) -> Term {
+        enum Frame {
+            Visit(TermId),
+            Apply,
+            Lambda,
+        }
+
+        let mut frames = vec![Frame::Visit(id)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(id) => match self.node(id) {
+                    Node::Var(n) => results.push(Term::var(n)),
+                    Node::Apply(t1, t2) => {
+                        frames.push(Frame::Apply);
+                        frames.push(Frame::Visit(t2));
+                        frames.push(Frame::Visit(t1));
+                    },
+                    Node::Lambda(t) => {
+                        frames.push(Frame::Lambda);
+                        frames.push(Frame::Visit(t));
+                    },
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(Term::apply(t1, t2));
+                },
+                Frame::Lambda => {
+                    let t = results.pop().unwrap();
+                    results.push(Term::lambda(t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+
+    /// Връща нормалната форма на терма с номер `id`, получена с нормалната
+    /// стратегия, като прави не повече от `fuel` нови β-стъпки.
+    ///
+    /// Нормалните форми на всички нормализирани подтермове се запомнят.
+    /// Връща `None`, ако стъпките не са стигнали.
+    pub fn normalize(&mut self, id: TermId, fuel: usize) -> Option<TermId> {
+        enum Frame {
+            Visit(TermId),
+            /// Нормалната форма на `id` е апликация на двата последни
+            /// резултата.
+            Apply(TermId),
+            /// Нормалната форма на `id` е абстракция с тяло последния
+            /// резултат.
+            Lambda(TermId),
+            /// Нормалната форма на `id` е последният резултат.
+            Same(TermId),
+        }
+
+        let mut fuel = fuel;
+        let mut frames = vec![Frame::Visit(id)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            let (id, result) = match frame {
+                Frame::Visit(id) => {
+                    if let Some(&result) = self.normal_forms.get(&id) {
+                        results.push(result);
+                        continue;
+                    }
+
+                    match self.node(id) {
+                        Node::Var(_) => (id, id),
+                        Node::Lambda(t) => {
+                            frames.push(Frame::Lambda(id));
+                            frames.push(Frame::Visit(t));
+                            continue;
+                        },
+                        Node::Apply(_, _) => {
+                            let head = self.whnf(id, &mut fuel)?;
+
+                            match self.node(head) {
+                                Node::Apply(t1, t2) => {
+                                    frames.push(Frame::Apply(id));
+                                    frames.push(Frame::Visit(t2));
+                                    frames.push(Frame::Visit(t1));
+                                },
+                                _ => {
+                                    frames.push(Frame::Same(id));
+                                    frames.push(Frame::Visit(head));
+                                },
+                            }
+                            continue;
+                        },
+                    }
+                },
+                Frame::Apply(id) => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    (id, self.apply(t1, t2))
+                },
+                Frame::Lambda(id) => {
+                    let t = results.pop().unwrap();
+                    (id, self.lambda(t))
+                },
+                Frame::Same(id) => (id, results.pop().unwrap()),
+            };
+
+            self.normal_forms.insert(id, result);
+            results.push(result);
+        }
+
+        results.pop()
+    }
+
+    /// Редуцира терма до слаба глава нормална форма с извикване по име.
+    ///
+    /// Аргументите на главата се пазят в `spine`, така че всяка β-стъпка е
+    /// една итерация на цикъла.
+    fn whnf(&mut self, id: TermId, fuel: &mut usize) -> Option<TermId> {
+        let mut head = id;
+        let mut spine = Vec::new();
+
+        loop {
+            match self.node(head) {
+                Node::Apply(t1, t2) => {
+                    spine.push(t2);
+                    head = t1;
+                },
+                Node::Lambda(body) if !spine.is_empty() => {
+                    if *fuel == 0 {
+                        return None;
+                    }
+                    *fuel -= 1;
+                    self.beta_steps += 1;
+
+                    let arg = spine.pop().unwrap();
+                    head = self.instantiate(body, arg, 0);
+                },
+                _ => break,
+            }
+        }
+
+        while let Some(arg) = spine.pop() {
+            head = self.apply(head, arg);
+        }
+
+        Some(head)
+    }
+
+    /// Заменя променливата с индекс `depth` в `id` с `arg` (с коригирани
+    /// индекси) и намалява с единица индексите на по-големите свободни
+    /// променливи, т.е. извършва β-контракцията на `(λ id) arg` под `depth`
+    /// абстракции.
+    fn instantiate(&mut self, id: TermId, arg: TermId, depth: usize) -> TermId {
+        self.map_free(id, depth, |store, n, depth| {
+            if n == depth {
+                store.shift(arg, 0, depth)
+            } else {
+                store.var(n - 1)
+            }
+        })
+    }
+
+    /// Увеличава с `by` индексите на свободните спрямо първите `from`
+    /// абстракции променливи.
+    fn shift(&mut self, id: TermId, from: usize, by: usize) -> TermId {
+        if by == 0 {
+            return id;
+        }
+
+        self.map_free(id, from, |store, n, _| store.var(n + by))
+    }
+
+    /// Копира терма, като заменя всяка променлива `n`, свободна спрямо
+    /// първите `from` абстракции и намираща се под `depth` абстракции
+    /// (заедно с първите `from`), с `f(self, n, depth)`.
+    ///
+    /// Подтермовете без такива променливи не се обхождат, а вече
+    /// обработените подтермове се взимат от кеш.
+    fn map_free<F>(&mut self, id: TermId, from: usize, mut f: F) -> TermId
+    where
+        F: FnMut(&mut Self, usize, usize) -> TermId,
+    {
+        enum Frame {
+            Visit(TermId, usize),
+            Apply(TermId, usize),
+            Lambda(TermId, usize),
+        }
+
+        let mut cache = HashMap::new();
+        let mut frames = vec![Frame::Visit(id, from)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            let (id, depth, result) = match frame {
+                Frame::Visit(id, depth) => {
+                    if self.free[id.0] <= depth {
+                        results.push(id);
+                        continue;
+                    }
+
+                    if let Some(&result) = cache.get(&(id, depth)) {
+                        results.push(result);
+                        continue;
+                    }
+
+                    match self.node(id) {
+                        Node::Var(n) => (id, depth, f(self, n, depth)),
+                        Node::Apply(t1, t2) => {
+                            frames.push(Frame::Apply(id, depth));
+                            frames.push(Frame::Visit(t2, depth));
+                            frames.push(Frame::Visit(t1, depth));
+                            continue;
+                        },
+                        Node::Lambda(t) => {
+                            frames.push(Frame::Lambda(id, depth));
+                            frames.push(Frame::Visit(t, depth + 1));
+                            continue;
+                        },
+                    }
+                },
+                Frame::Apply(id, depth) => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    (id, depth, self.apply(t1, t2))
+                },
+                Frame::Lambda(id, depth) => {
+                    let t = results.pop().unwrap();
+                    (id, depth, self.lambda(t))
+                },
+            };
+
+            cache.insert((id, depth), result);
+            results.push(result);
+        }
+
+        results.pop().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,89 @@
+use super::Store;
+use crate::fixtures::{self, church};
+use crate::unnamed::{Strategy, Term};
+use crate::unnamed_term;
+
+#[test]
+fn intern() {
+    let mut store = Store::new();
+    let term = unnamed_term!((λ 0 1) (λ 0 1));
+
+    let id = store.intern(&term);
+    assert_eq!(store.to_term(id), term);
+    assert_eq!(store.len(), 5);
+
+    assert_eq!(store.intern(&unnamed_term!((λ 0 1) (λ 0 1))), id);
+    assert_ne!(store.intern(&unnamed_term!((λ 0 1) (λ 1 0))), id);
+    assert_eq!(store.intern(&unnamed_term!(λ 0 1)), store.intern(&unnamed_term!(λ 0 1)));
+}
+
+#[test]
+fn agrees_with_normal_order() {
+    let mut store = Store::new();
+    for term in fixtures::sample_terms() {
+        let id = store.intern(&term);
+
+        assert_eq!(
+            store.normalize(id, 1000).map(|id| store.to_term(id)),
+            term.normalize(Strategy::NormalOrder, 1000, false),
+            "{}",
+            term
+        );
+    }
+}
+
+#[test]
+fn fuel() {
+    let mut store = Store::new();
+
+    let omega = store.intern(&unnamed_term!((λ 0 0) (λ 0 0)));
+    assert_eq!(store.normalize(omega, 1000), None);
+    assert_eq!(store.normalize(omega, 1_000_000), None);
+
+    let id = store.intern(&unnamed_term!((λ 0) ((λ 0) 1)));
+    assert_eq!(store.normalize(id, 1), None);
+    let normal_form = store.normalize(id, 2).unwrap();
+    assert_eq!(store.to_term(normal_form), unnamed_term!(1));
+}
+
+#[test]
+fn memoization() {
+    let plus = unnamed_term!(λ λ λ λ 3 1 (2 1 0));
+    let sum = Term::apply(Term::apply(plus, church(2)), church(3));
+
+    let mut store = Store::new();
+    let id = store.intern(&sum);
+    let normal_form = store.normalize(id, 100).unwrap();
+    assert_eq!(store.to_term(normal_form), church(5));
+
+    let steps = store.beta_steps();
+    assert!(steps > 0);
+    assert_eq!(store.normalize(id, 0), Some(normal_form));
+    assert_eq!(store.beta_steps(), steps);
+
+    // Двете копия на сбора имат една и съща нормална форма, която се
+    // пресмята само веднъж.
+    let twice = store.intern(&Term::apply(Term::apply(Term::var(0), sum.clone()), sum));
+    let normal_form = store.normalize(twice, 0).unwrap();
+    assert_eq!(
+        store.to_term(normal_form),
+        Term::apply(Term::apply(Term::var(0), church(5)), church(5))
+    );
+    assert_eq!(store.beta_steps(), steps);
+}
+
+#[test]
+fn deep_terms() {
+    const DEPTH: usize = 100_000;
+
+    let mut store = Store::new();
+    let lambdas = (0..DEPTH).fold(Term::var(DEPTH), |t, _| Term::lambda(t));
+    let id = store.intern(&lambdas);
+    assert_eq!(store.to_term(id), lambdas);
+    assert_eq!(store.normalize(id, 0), Some(id));
+
+    let redexes = (0..DEPTH).fold(Term::var(0), |t, _| Term::apply(unnamed_term!(λ 0), t));
+    let id = store.intern(&redexes);
+    let normal_form = store.normalize(id, DEPTH).unwrap();
+    assert_eq!(store.to_term(normal_form), Term::var(0));
+}
@@ -0,0 +1,231 @@
+//! Общ интерфейс на представянията на ламбда термове.
+//!
+//! Функциите в този модул работят с всяко представяне, което имплементира
+//! `LambdaTerm`, така че не е нужно да се пишат отделно за именувани и
+//! безименни термове.
+
+use std::fmt::{self, Debug, Display};
+
+/// Изглед към върха на терм, общ за всички представяния.
+#[derive(Debug)]
+pub enum View<'a, T: LambdaTerm> {
+    Var(&'a T::Var),
+    Apply(&'a T, &'a T),
+    Lambda(&'a T::Binder, &'a T),
+}
+
+/// Като `View`, но с изменими референции към децата на върха.
+#[derive(Debug)]
+pub enum ViewMut<'a, T: LambdaTerm> {
+    Var(&'a mut T::Var),
+    Apply(&'a mut T, &'a mut T),
+    Lambda(&'a T::Binder, &'a mut T),
+}
+
+/// Представяне на ламбда термове.
+///
+/// `Var` е типът на променливите (индекс или име), а `Binder` - това, което
+/// абстракцията пази за свързаната си променлива (`()` за безименните
+/// термове).
+///
+/// # Пример
+///
+/// ```
+/// use hw::lambda::{self, LambdaTerm};
+/// use hw::named::Term as NamedTerm;
+/// use hw::unnamed::Term as UnnamedTerm;
+/// use hw::{term, unnamed_term};
+///
+/// fn identity<T: LambdaTerm>(binder: T::Binder, var: T::Var) -> T {
+///     T::lambda(binder, T::var(var))
+/// }
+///
+/// assert_eq!(identity::<NamedTerm>("x".into(), "x".into()), term!(λ x. x));
+/// assert_eq!(identity::<UnnamedTerm>((), 0), unnamed_term!(λ 0));
+///
+/// assert_eq!(lambda::size(&term!(λ x. x y)), lambda::size(&unnamed_term!(λ 0 1)));
+/// ```
+pub trait LambdaTerm: Sized {
+    type Var;
+    type Binder;
+
+    fn var(var: Self::Var) -> Self;
+    fn apply(t1: Self, t2: Self) -> Self;
+    fn lambda(binder: Self::Binder, body: Self) -> Self;
+
+    fn view(&self) -> View<'_, Self>;
+    fn view_mut(&mut self) -> ViewMut<'_, Self>;
+
+    /// Принтира началото на абстракция, свързваща `binder`, заедно с
+    /// разделителя преди тялото ѝ.
+    fn fmt_binder(binder: &Self::Binder, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// Връща броя на върховете на терма.
+pub fn size<T: LambdaTerm>(term: &T) -> usize {
+    let mut size = 0;
+    let mut stack = vec![term];
+
+    while let Some(term) = stack.pop() {
+        size += 1;
+
+        match term.view() {
+            View::Var(_) => {},
+            View::Apply(t1, t2) => {
+                stack.push(t2);
+                stack.push(t1);
+            },
+            View::Lambda(_, t) => stack.push(t),
+        }
+    }
+
+    size
+}
+
+/// Връща височината на терма, т.е. броя на върховете в най-дългия път от
+/// корена до променлива.
+pub fn height<T: LambdaTerm>(term: &T) -> usize {
+    let mut height = 0;
+    let mut stack = vec![(term, 1)];
+
+    while let Some((term, depth)) = stack.pop() {
+        height = height.max(depth);
+
+        match term.view() {
+            View::Var(_) => {},
+            View::Apply(t1, t2) => {
+                stack.push((t2, depth + 1));
+                stack.push((t1, depth + 1));
+            },
+            View::Lambda(_, t) => stack.push((t, depth + 1)),
+        }
+    }
+
+    height
+}
+
+/// Връща броя на β-редексите в терма.
+pub fn redex_count<T: LambdaTerm>(term: &T) -> usize {
+    let mut count = 0;
+    let mut stack = vec![term];
+
+    while let Some(term) = stack.pop() {
+        match term.view() {
+            View::Var(_) => {},
+            View::Apply(t1, t2) => {
+                if let View::Lambda(_, _) = t1.view() {
+                    count += 1;
+                }
+
+                stack.push(t2);
+                stack.push(t1);
+            },
+            View::Lambda(_, t) => stack.push(t),
+        }
+    }
+
+    count
+}
+
+/// Принтира терма с минимален брой скоби - апликацията е лявоасоциативна, а
+/// тялото на абстракцията се простира възможно най-надясно.
+///
+/// Използва се от `Display` на всички представяния.
+pub fn fmt<T>(term: &T, f: &mut fmt::Formatter) -> fmt::Result
+where
+    T: LambdaTerm,
+    T::Var: Display,
+{
+    enum Item<'a, T> {
+        Term(&'a T),
+        Text(&'static str),
+    }
+
+    let mut stack = vec![Item::Term(term)];
+
+    while let Some(item) = stack.pop() {
+        let term = match item {
+            Item::Text(text) => {
+                write!(f, "{}", text)?;
+                continue;
+            },
+            Item::Term(term) => term,
+        };
+
+        match term.view() {
+            View::Var(x) => write!(f, "{}", x)?,
+            View::Apply(t1, t2) => {
+                match t2.view() {
+                    View::Var(_) => stack.push(Item::Term(t2)),
+                    _ => stack.extend(vec![Item::Text(")"), Item::Term(t2), Item::Text("(")]),
+                }
+
+                stack.push(Item::Text(" "));
+
+                match t1.view() {
+                    View::Lambda(_, _) => {
+                        stack.extend(vec![Item::Text(")"), Item::Term(t1), Item::Text("(")])
+                    },
+                    _ => stack.push(Item::Term(t1)),
+                }
+            },
+            View::Lambda(binder, t) => {
+                T::fmt_binder(binder, f)?;
+                stack.push(Item::Term(t));
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Принтира терма във вида на `#[derive(Debug)]` - `Var(x)`, `Apply(M, N)`
+/// и `Lambda(M)`, като името на абстракцията (ако има) се принтира с
+/// `fmt_binder` преди тялото ѝ.
+///
+/// Използва явен стек, така че се използва от `Debug` на представянията,
+/// които трябва да работят и за много дълбоки термове.
+pub fn fmt_debug<T>(
+    term: &T,
+    f: &mut fmt::Formatter,
+    fmt_binder: fn(&T::Binder, &mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result
+where
+    T: LambdaTerm,
+    T::Var: Debug,
+{
+    enum Item<'a, T> {
+        Term(&'a T),
+        Text(&'static str),
+    }
+
+    let mut stack = vec![Item::Term(term)];
+
+    while let Some(item) = stack.pop() {
+        match item {
+            Item::Text(text) => write!(f, "{}", text)?,
+            Item::Term(term) => match term.view() {
+                View::Var(x) => write!(f, "Var({:?})", x)?,
+                View::Apply(t1, t2) => {
+                    write!(f, "Apply(")?;
+                    stack.extend(vec![
+                        Item::Text(")"),
+                        Item::Term(t2),
+                        Item::Text(", "),
+                        Item::Term(t1),
+                    ]);
+                },
+                View::Lambda(binder, t) => {
+                    write!(f, "Lambda(")?;
+                    fmt_binder(binder, f)?;
+                    stack.extend(vec![Item::Text(")"), Item::Term(t)]);
+                },
+            },
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,61 @@
+use super::{height, redex_count, size, LambdaTerm, View};
+use crate::fixtures::church_in;
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use crate::{term, unnamed_term};
+
+#[test]
+fn constructors() {
+    let named: NamedTerm = church_in(2, ("f".into(), "f".into()), ("x".into(), "x".into()));
+    let unnamed: UnnamedTerm = church_in(2, ((), 1), ((), 0));
+
+    assert_eq!(named, term!(λ f. λ x. f (f x)));
+    assert_eq!(unnamed, unnamed_term!(λ λ 1 (1 0)));
+    assert_eq!(UnnamedTerm::from_named(&named).0, unnamed);
+}
+
+#[test]
+fn view() {
+    let term = term!((λ x. x) y);
+
+    match term.view() {
+        View::Apply(t1, t2) => {
+            assert!(matches!(t1.view(), View::Lambda(x, _) if x == "x"));
+            assert!(matches!(t2.view(), View::Var(y) if y == "y"));
+        },
+        _ => panic!("expected application"),
+    }
+
+    assert!(matches!(unnamed_term!(λ 0).view(), View::Lambda((), _)));
+}
+
+#[test]
+fn metrics() {
+    let cases = vec![
+        (term!(x), unnamed_term!(0), (1, 1, 0)),
+        (term!(λ x. x y), unnamed_term!(λ 0 1), (4, 3, 0)),
+        (term!((λ x. x) ((λ y. y) z)), unnamed_term!((λ 0) ((λ 0) 2)), (7, 4, 2)),
+        (term!(λ f. λ x. f (f x)), unnamed_term!(λ λ 1 (1 0)), (7, 5, 0)),
+    ];
+
+    for (named, unnamed, expected) in cases {
+        assert_eq!((size(&named), height(&named), redex_count(&named)), expected, "{}", named);
+        assert_eq!((size(&unnamed), height(&unnamed), redex_count(&unnamed)), expected);
+    }
+}
+
+#[test]
+fn display() {
+    let cases = vec![
+        (term!(x y z), unnamed_term!(0 1 2), "x y z", "0 1 2"),
+        (term!(x (y z)), unnamed_term!(0 (1 2)), "x (y z)", "0 (1 2)"),
+        (term!((λ x. x) y), unnamed_term!((λ 0) 1), "(λ x. x) y", "(λ 0) 1"),
+        (term!(x (λ y. y)), unnamed_term!(0 (λ 0)), "x (λ y. y)", "0 (λ 0)"),
+        (term!(λ x. λ y. x y), unnamed_term!(λ λ 1 0), "λ x. λ y. x y", "λ λ 1 0"),
+    ];
+
+    for (named, unnamed, named_text, unnamed_text) in cases {
+        assert_eq!(named.to_string(), named_text);
+        assert_eq!(unnamed.to_string(), unnamed_text);
+    }
+}
@@ -0,0 +1,255 @@
+//! Ламбда термове с индекси на де Брьойн, броени отвън навътре (нива).
+//!
+//! Терм се разглежда в контекст от `free` свободни променливи с нива
+//! `0, 1, ..., free - 1`, като най-външната има ниво 0. Абстракция, над
+//! която има `d` други абстракции, свързва променливата с ниво `free + d`.
+//! Така една и съща променлива има едно и също ниво навсякъде в терма и
+//! при влизане под абстракция нивата не се променят.
+
+use crate::lambda::{self, LambdaTerm, View, ViewMut};
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use crate::visit::Scoped;
+use std::fmt::{self, Display};
+
+/// Ламбда терм с нива на де Брьойн
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Term {
+    Var(usize),
+    Apply(Box<Term>, Box<Term>),
+    Lambda(Box<Term>),
+}
+
+impl Term {
+    pub fn var(n: usize) -> Self {
+        Term::Var(n)
+    }
+
+    pub fn apply(t1: Term, t2: Term) -> Self {
+        Term::Apply(Box::new(t1), Box::new(t2))
+    }
+
+    pub fn lambda(t: Term) -> Self {
+        Term::Lambda(Box::new(t))
+    }
+
+    /// Създава терм с нива от безименен терм в контекст от `free` свободни
+    /// променливи. Свободната променлива с индекс `i` получава ниво
+    /// `free - 1 - i`.
+    ///
+    /// # Panics
+    ///
+    /// Ако терма има свободна променлива с индекс поне `free`.
+    pub fn from_unnamed(unnamed: &UnnamedTerm, free: usize) -> Self {
+        Self::from_unnamed_inner(unnamed, free)
+    }
+
+    fn from_unnamed_inner(unnamed: &UnnamedTerm, context: usize) -> Self {
+        match unnamed {
+            UnnamedTerm::Var(i) => Self::var(
+                context
+                    .checked_sub(i + 1)
+                    .expect("free variable outside of the context"),
+            ),
+            UnnamedTerm::Apply(t1, t2) => Self::apply(
+                Self::from_unnamed_inner(t1, context),
+                Self::from_unnamed_inner(t2, context),
+            ),
+            UnnamedTerm::Lambda(t) => Self::lambda(Self::from_unnamed_inner(t, context + 1)),
+        }
+    }
+
+    /// Превръща терма в безименен, като го разглежда в контекст от `free`
+    /// свободни променливи. Обратна операция на `from_unnamed`.
+    ///
+    /// # Panics
+    ///
+    /// Ако терма има свободна променлива с ниво поне `free`.
+    pub fn to_unnamed(&self, free: usize) -> UnnamedTerm {
+        match self {
+            Term::Var(level) => UnnamedTerm::var(
+                free
+                    .checked_sub(level + 1)
+                    .expect("free variable outside of the context"),
+            ),
+            Term::Apply(t1, t2) => UnnamedTerm::apply(t1.to_unnamed(free), t2.to_unnamed(free)),
+            Term::Lambda(t) => UnnamedTerm::lambda(t.to_unnamed(free + 1)),
+        }
+    }
+
+    /// Създава терм с нива от именуван.
+    ///
+    /// Връща новия терм и имената на свободните променливи, подредени по
+    /// нива - името на ниво `l` е на позиция `l`.
+    pub fn from_named(named: &NamedTerm) -> (Self, Vec<String>) {
+        let (unnamed, mut names) = UnnamedTerm::from_named(named);
+        let term = Self::from_unnamed(&unnamed, names.len());
+
+        names.reverse();
+        (term, names)
+    }
+
+    /// Превръща терма в именуван в контекст от `free` свободни променливи,
+    /// като имената се генерират както в `named::Term::from_unnamed`.
+    pub fn to_named(&self, free: usize) -> NamedTerm {
+        NamedTerm::from_unnamed(&self.to_unnamed(free))
+    }
+
+    /// Изпълнява субституцията `term[level -> subs]`, където `term` и `subs`
+    /// са в контекст от `free` свободни променливи.
+    ///
+    /// Свободните променливи на `subs` запазват нивата си, колкото и дълбоко
+    /// да е заместването. Променят се само нивата, свързани от абстракциите
+    /// в самия `subs` - веднъж при всяко заместване, а не при всяка
+    /// абстракция по пътя.
+    pub fn substitute(&self, level: usize, subs: &Term, free: usize) -> Term {
+        self.substitute_inner(level, subs, free, 0)
+    }
+
+    fn substitute_inner(&self, level: usize, subs: &Term, free: usize, depth: usize) -> Term {
+        match self {
+            Term::Var(x) if *x == level => subs.shift_bound(free, depth),
+            Term::Var(x) => Term::var(*x),
+            Term::Apply(t1, t2) => Term::apply(
+                t1.substitute_inner(level, subs, free, depth),
+                t2.substitute_inner(level, subs, free, depth),
+            ),
+            Term::Lambda(t) => Term::lambda(t.substitute_inner(level, subs, free, depth + 1)),
+        }
+    }
+
+    /// Увеличава с `by` нивата, които са поне `from`.
+    fn shift_bound(&self, from: usize, by: usize) -> Term {
+        match self {
+            Term::Var(x) if *x >= from => Term::var(x + by),
+            Term::Var(x) => Term::var(*x),
+            Term::Apply(t1, t2) => {
+                Term::apply(t1.shift_bound(from, by), t2.shift_bound(from, by))
+            },
+            Term::Lambda(t) => Term::lambda(t.shift_bound(from, by)),
+        }
+    }
+
+    /// Свива редекса `(λ body) arg` в контекст от `free` свободни
+    /// променливи. Връща `None`, ако терма не е редекс.
+    pub fn contract(&self, free: usize) -> Option<Term> {
+        match self {
+            Term::Apply(t1, arg) => match &**t1 {
+                Term::Lambda(body) => Some(body.instantiate(free, arg, 0)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Заменя свързаната от премахнатата абстракция променлива `level` с
+    /// `arg` и намалява с единица нивата на по-вътрешните абстракции.
+    fn instantiate(&self, level: usize, arg: &Term, depth: usize) -> Term {
+        match self {
+            Term::Var(x) if *x == level => arg.shift_bound(level, depth),
+            Term::Var(x) if *x > level => Term::var(x - 1),
+            Term::Var(x) => Term::var(*x),
+            Term::Apply(t1, t2) => Term::apply(
+                t1.instantiate(level, arg, depth),
+                t2.instantiate(level, arg, depth),
+            ),
+            Term::Lambda(t) => Term::lambda(t.instantiate(level, arg, depth + 1)),
+        }
+    }
+
+    /// Прави една стъпка с нормалната стратегия в контекст от `free`
+    /// свободни променливи.
+    pub fn reduce_step(&self, free: usize) -> Option<Term> {
+        match self {
+            Term::Var(_) => None,
+            Term::Apply(t1, t2) => match &**t1 {
+                Term::Lambda(_) => self.contract(free),
+                _ => match t1.reduce_step(free) {
+                    Some(t1) => Some(Term::apply(t1, (**t2).clone())),
+                    None => t2.reduce_step(free).map(|t2| Term::apply((**t1).clone(), t2)),
+                },
+            },
+            Term::Lambda(t) => t.reduce_step(free + 1).map(Term::lambda),
+        }
+    }
+
+    /// Нормализира терма с нормалната стратегия, като прави не повече от
+    /// `fuel` стъпки. Връща `None`, ако стъпките не са стигнали.
+    pub fn normalize(&self, free: usize, fuel: usize) -> Option<Term> {
+        let mut term = self.clone();
+
+        for _ in 0..fuel {
+            match term.reduce_step(free) {
+                Some(next) => term = next,
+                None => return Some(term),
+            }
+        }
+
+        match term.reduce_step(free) {
+            Some(_) => None,
+            None => Some(term),
+        }
+    }
+}
+
+impl LambdaTerm for Term {
+    type Var = usize;
+    type Binder = ();
+
+    fn var(var: usize) -> Self {
+        Term::var(var)
+    }
+
+    fn apply(t1: Term, t2: Term) -> Self {
+        Term::apply(t1, t2)
+    }
+
+    fn lambda(_: (), body: Term) -> Self {
+        Term::lambda(body)
+    }
+
+    fn view(&self) -> View<'_, Self> {
+        match self {
+            Term::Var(x) => View::Var(x),
+            Term::Apply(t1, t2) => View::Apply(t1, t2),
+            Term::Lambda(t) => View::Lambda(&(), t),
+        }
+    }
+
+    fn view_mut(&mut self) -> ViewMut<'_, Self> {
+        match self {
+            Term::Var(x) => ViewMut::Var(x),
+            Term::Apply(t1, t2) => ViewMut::Apply(t1, t2),
+            Term::Lambda(t) => ViewMut::Lambda(&(), t),
+        }
+    }
+
+    fn fmt_binder(_: &(), f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "λ ")
+    }
+}
+
+/// Обхватът на терм с нива е броят на абстракциите над върха.
+impl Scoped for Term {
+    type Scope = usize;
+
+    fn enter(depth: &mut usize, _: &()) {
+        *depth += 1;
+    }
+
+    fn exit(depth: &mut usize) {
+        *depth -= 1;
+    }
+}
+
+/// Формат за принтиране.
+///
+/// Използва се от `println!("{}", ...)`
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lambda::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use super::Term;
+use crate::arbitrary;
+use crate::named::Term as NamedTerm;
+use crate::unnamed::{Strategy, Term as UnnamedTerm};
+use crate::{term, unnamed_term};
+use proptest::prelude::*;
+
+#[test]
+fn conversion() {
+    assert_eq!(Term::from_unnamed(&unnamed_term!(λ λ 1 0), 0), term_levels(&[0, 1]));
+    assert_eq!(Term::from_unnamed(&unnamed_term!(λ 0 1 2), 2).to_string(), "λ 2 1 0");
+
+    let (term, names) = Term::from_named(&term!(λ x. x y z));
+    assert_eq!(term.to_string(), "λ 2 1 0");
+    assert_eq!(names, vec!["z", "y"]);
+    assert_eq!(term.to_named(2), term!(λ x. x a b));
+}
+
+/// `λ λ l1 l2`
+fn term_levels(levels: &[usize; 2]) -> Term {
+    Term::lambda(Term::lambda(Term::apply(Term::var(levels[0]), Term::var(levels[1]))))
+}
+
+#[test]
+fn levels_are_stable_under_binders() {
+    let term = Term::from_unnamed(&unnamed_term!(0 (λ 1 (λ 2))), 1);
+    assert_eq!(term.to_string(), "0 (λ 0 (λ 0))");
+}
+
+#[test]
+#[should_panic(expected = "free variable outside of the context")]
+fn conversion_outside_of_context() {
+    Term::from_unnamed(&unnamed_term!(λ 2), 1);
+}
+
+#[test]
+#[should_panic(expected = "free variable outside of the context")]
+fn to_unnamed_outside_of_context() {
+    Term::lambda(Term::var(2)).to_unnamed(1);
+}
+
+#[test]
+fn normalize_with_free_variables() {
+    let term = Term::from_unnamed(&unnamed_term!((λ λ 1 0 2) (λ 0) 5), 6);
+    assert_eq!(term.normalize(6, 10).unwrap().to_unnamed(6), unnamed_term!(5 0));
+}
+
+// Случайни термове в контекст от три свободни променливи.
+proptest! {
+    #[test]
+    fn roundtrip(term in arbitrary::unnamed_term(3, 8)) {
+        prop_assert_eq!(Term::from_unnamed(&term, 3).to_unnamed(3), term.clone());
+
+        let named = NamedTerm::from_unnamed(&term);
+        let (levels, names) = Term::from_named(&named);
+        let (unnamed, mut unnamed_names) = UnnamedTerm::from_named(&named);
+        unnamed_names.reverse();
+
+        prop_assert_eq!(&names, &unnamed_names);
+        prop_assert_eq!(levels.to_unnamed(names.len()), unnamed);
+    }
+
+    #[test]
+    fn substitute_agrees_with_indices(
+        term in arbitrary::unnamed_term(3, 8),
+        subs in arbitrary::unnamed_term(3, 4),
+        index in 0..3usize,
+    ) {
+        let levels = Term::from_unnamed(&term, 3).substitute(
+            2 - index,
+            &Term::from_unnamed(&subs, 3),
+            3,
+        );
+        prop_assert_eq!(levels.to_unnamed(3), term.substitute(index, &subs));
+    }
+
+    #[test]
+    fn normalize_agrees_with_indices(term in arbitrary::unnamed_term(3, 8)) {
+        let expected = term.normalize(Strategy::NormalOrder, 100, false);
+        let levels = Term::from_unnamed(&term, 3).normalize(3, 100);
+
+        prop_assert_eq!(levels.map(|t| t.to_unnamed(3)), expected);
+    }
+}
@@ -1,2 +1,20 @@
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod arbitrary;
+pub mod arena;
+pub mod development;
+pub mod enumeration;
+pub mod fixtures;
+pub mod graph;
+pub mod hashcons;
+pub mod lambda;
+pub mod levels;
+pub mod locally_nameless;
+pub mod machine;
 pub mod named;
+pub mod nbe;
+pub mod optimal;
+pub mod parallel;
+pub mod path;
+pub mod sigma;
 pub mod unnamed;
+pub mod visit;
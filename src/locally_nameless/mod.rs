@@ -0,0 +1,358 @@
+//! Локално безименно представяне на ламбда термове.
+//!
+//! Свързаните променливи са индекси на де Брьойн, а свободните - имена.
+//! Така термовете нямат нужда от отделен контекст от имена, а
+//! α-еквивалентните термове са равни.
+
+use crate::lambda::{self, LambdaTerm, View, ViewMut};
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+
+/// Променлива в локално безименен терм.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Var {
+    /// Свързана променлива, зададена с индекс на де Брьойн.
+    BVar(usize),
+    /// Свободна променлива.
+    FVar(String),
+}
+
+/// Локално безименен ламбда терм
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Term {
+    Var(Var),
+    Apply(Box<Term>, Box<Term>),
+    Lambda(Box<Term>),
+}
+
+impl Term {
+    pub fn var(var: Var) -> Self {
+        Term::Var(var)
+    }
+
+    pub fn bvar(n: usize) -> Self {
+        Term::Var(Var::BVar(n))
+    }
+
+    pub fn fvar<S>(s: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Term::Var(Var::FVar(s.into()))
+    }
+
+    pub fn apply(t1: Term, t2: Term) -> Self {
+        Term::Apply(Box::new(t1), Box::new(t2))
+    }
+
+    pub fn lambda(t: Term) -> Self {
+        Term::Lambda(Box::new(t))
+    }
+
+    /// Създава локално безименен терм от именуван.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::locally_nameless::Term;
+    /// use hw::term;
+    ///
+    /// let term = Term::from_named(&term!(λ x. x y));
+    /// assert_eq!(term, Term::lambda(Term::apply(Term::bvar(0), Term::fvar("y"))));
+    /// assert_eq!(term, Term::from_named(&term!(λ z. z y)));
+    /// ```
+    pub fn from_named(named: &NamedTerm) -> Self {
+        let (unnamed, names) = UnnamedTerm::from_named(named);
+        Self::from_unnamed(&unnamed, &names)
+    }
+
+    /// Създава локално безименен терм от безименен, като свободната
+    /// променлива с индекс `i` получава името `names[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Ако терма има свободна променлива с индекс поне `names.len()`.
+    pub fn from_unnamed(unnamed: &UnnamedTerm, names: &[String]) -> Self {
+        Self::from_unnamed_inner(unnamed, names, 0)
+    }
+
+    fn from_unnamed_inner(unnamed: &UnnamedTerm, names: &[String], depth: usize) -> Self {
+        match unnamed {
+            UnnamedTerm::Var(i) if *i < depth => Self::bvar(*i),
+            UnnamedTerm::Var(i) => Self::fvar(names[i - depth].clone()),
+            UnnamedTerm::Apply(t1, t2) => Self::apply(
+                Self::from_unnamed_inner(t1, names, depth),
+                Self::from_unnamed_inner(t2, names, depth),
+            ),
+            UnnamedTerm::Lambda(t) => Self::lambda(Self::from_unnamed_inner(t, names, depth + 1)),
+        }
+    }
+
+    /// Превръща терма в именуван. Свързаните променливи получават
+    /// автоматично генерирани имена, различни от свободните променливи в
+    /// тялото на абстракцията.
+    ///
+    /// # Panics
+    ///
+    /// Ако терма не е локално затворен.
+    pub fn to_named(&self) -> NamedTerm {
+        match self {
+            Term::Var(Var::BVar(_)) => panic!("term is not locally closed"),
+            Term::Var(Var::FVar(x)) => NamedTerm::var(x.clone()),
+            Term::Apply(t1, t2) => NamedTerm::apply(t1.to_named(), t2.to_named()),
+            Term::Lambda(t) => {
+                let name = t.fresh_name();
+                NamedTerm::lambda(name.clone(), t.open(&Term::fvar(name)).to_named())
+            },
+        }
+    }
+
+    /// Превръща терма в безименен. Свободната променлива `x` получава индекс,
+    /// равен на позицията на `x` в `names`, като липсващите имена се
+    /// добавят в края, както в `unnamed::Term::from_named_with`.
+    pub fn to_unnamed(&self, names: &mut Vec<String>) -> UnnamedTerm {
+        self.to_unnamed_inner(names, 0)
+    }
+
+    fn to_unnamed_inner(&self, names: &mut Vec<String>, depth: usize) -> UnnamedTerm {
+        match self {
+            Term::Var(Var::BVar(i)) => UnnamedTerm::var(*i),
+            Term::Var(Var::FVar(x)) => match names.iter().position(|name| name == x) {
+                Some(index) => UnnamedTerm::var(depth + index),
+                None => {
+                    names.push(x.clone());
+                    UnnamedTerm::var(depth + names.len() - 1)
+                },
+            },
+            Term::Apply(t1, t2) => UnnamedTerm::apply(
+                t1.to_unnamed_inner(names, depth),
+                t2.to_unnamed_inner(names, depth),
+            ),
+            Term::Lambda(t) => UnnamedTerm::lambda(t.to_unnamed_inner(names, depth + 1)),
+        }
+    }
+
+    /// Отваря тялото на абстракция - заменя свързаната от нея променлива с
+    /// `with`. `with` трябва да е локално затворен.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::locally_nameless::Term;
+    /// use hw::term;
+    ///
+    /// let body = match Term::from_named(&term!(λ x. λ y. x y)) {
+    ///     Term::Lambda(body) => *body,
+    ///     _ => unreachable!(),
+    /// };
+    ///
+    /// let opened = body.open(&Term::fvar("z"));
+    /// assert_eq!(opened, Term::from_named(&term!(λ y. z y)));
+    /// assert_eq!(opened.close("z"), body);
+    /// ```
+    pub fn open(&self, with: &Term) -> Term {
+        self.open_at(0, with)
+    }
+
+    fn open_at(&self, depth: usize, with: &Term) -> Term {
+        match self {
+            Term::Var(Var::BVar(i)) if *i == depth => with.clone(),
+            Term::Var(_) => self.clone(),
+            Term::Apply(t1, t2) => Term::apply(t1.open_at(depth, with), t2.open_at(depth, with)),
+            Term::Lambda(t) => Term::lambda(t.open_at(depth + 1, with)),
+        }
+    }
+
+    /// Затваря терма по свободната променлива `name` - връща тяло на
+    /// абстракция, която свързва `name`. Обратна операция на `open`.
+    pub fn close(&self, name: &str) -> Term {
+        self.close_at(0, name)
+    }
+
+    fn close_at(&self, depth: usize, name: &str) -> Term {
+        match self {
+            Term::Var(Var::FVar(x)) if x == name => Term::bvar(depth),
+            Term::Var(_) => self.clone(),
+            Term::Apply(t1, t2) => Term::apply(t1.close_at(depth, name), t2.close_at(depth, name)),
+            Term::Lambda(t) => Term::lambda(t.close_at(depth + 1, name)),
+        }
+    }
+
+    /// Връща свободните променливи на терма.
+    pub fn free_vars(&self) -> BTreeSet<String> {
+        let mut fv = BTreeSet::new();
+        self.fill_free_vars(&mut fv);
+        fv
+    }
+
+    fn fill_free_vars(&self, fv: &mut BTreeSet<String>) {
+        match self {
+            Term::Var(Var::BVar(_)) => {},
+            Term::Var(Var::FVar(x)) => {
+                fv.insert(x.clone());
+            },
+            Term::Apply(t1, t2) => {
+                t1.fill_free_vars(fv);
+                t2.fill_free_vars(fv);
+            },
+            Term::Lambda(t) => t.fill_free_vars(fv),
+        }
+    }
+
+    /// Проверява дали всеки индекс в терма е свързан от абстракция.
+    pub fn is_locally_closed(&self) -> bool {
+        self.is_closed_at(0)
+    }
+
+    fn is_closed_at(&self, depth: usize) -> bool {
+        match self {
+            Term::Var(Var::BVar(i)) => *i < depth,
+            Term::Var(Var::FVar(_)) => true,
+            Term::Apply(t1, t2) => t1.is_closed_at(depth) && t2.is_closed_at(depth),
+            Term::Lambda(t) => t.is_closed_at(depth + 1),
+        }
+    }
+
+    /// Изпълнява субституцията `term[name -> subs]`. Тъй като свързаните
+    /// променливи нямат имена, не е нужно преименуване.
+    pub fn substitute(&self, name: &str, subs: &Term) -> Term {
+        match self {
+            Term::Var(Var::FVar(x)) if x == name => subs.clone(),
+            Term::Var(_) => self.clone(),
+            Term::Apply(t1, t2) => {
+                Term::apply(t1.substitute(name, subs), t2.substitute(name, subs))
+            },
+            Term::Lambda(t) => Term::lambda(t.substitute(name, subs)),
+        }
+    }
+
+    /// Заменя свързаната от премахнатата абстракция променлива (индекс
+    /// `depth`) с `with`, като намалява с единица индексите, свързани от
+    /// абстракции над нея. `with` е на дълбочина `depth` под тези
+    /// абстракции, така че несвързаните му индекси се увеличават с `depth`.
+    fn instantiate(&self, depth: usize, with: &Term) -> Term {
+        match self {
+            Term::Var(Var::BVar(i)) if *i == depth => with.shift(depth, 0),
+            Term::Var(Var::BVar(i)) if *i > depth => Term::bvar(i - 1),
+            Term::Var(_) => self.clone(),
+            Term::Apply(t1, t2) => {
+                Term::apply(t1.instantiate(depth, with), t2.instantiate(depth, with))
+            },
+            Term::Lambda(t) => Term::lambda(t.instantiate(depth + 1, with)),
+        }
+    }
+
+    /// Увеличава с `by` индексите, които не са свързани в терма, ако той е на
+    /// дълбочина `cutoff`.
+    fn shift(&self, by: usize, cutoff: usize) -> Term {
+        match self {
+            Term::Var(Var::BVar(i)) if *i >= cutoff => Term::bvar(i + by),
+            Term::Var(_) => self.clone(),
+            Term::Apply(t1, t2) => Term::apply(t1.shift(by, cutoff), t2.shift(by, cutoff)),
+            Term::Lambda(t) => Term::lambda(t.shift(by, cutoff + 1)),
+        }
+    }
+
+    /// Прави една стъпка с нормалната стратегия. Под абстракциите се
+    /// редуцира направо с индексите, без терма да се отваря - при β-редукция
+    /// на дълбочина `d` несвързаните индекси на аргумента се изместват с `d`.
+    pub fn reduce_step(&self) -> Option<Term> {
+        match self {
+            Term::Var(_) => None,
+            Term::Apply(t1, t2) => match &**t1 {
+                Term::Lambda(body) => Some(body.instantiate(0, t2)),
+                _ => match t1.reduce_step() {
+                    Some(t1) => Some(Term::apply(t1, (**t2).clone())),
+                    None => t2.reduce_step().map(|t2| Term::apply((**t1).clone(), t2)),
+                },
+            },
+            Term::Lambda(t) => t.reduce_step().map(Term::lambda),
+        }
+    }
+
+    /// Връща име, което не е свободна променлива в терма.
+    fn fresh_name(&self) -> String {
+        NamedTerm::fresh_name(&self.free_vars().into_iter().collect())
+    }
+
+    /// Нормализира терма с нормалната стратегия, като прави не повече от
+    /// `fuel` стъпки. Връща `None`, ако стъпките не са стигнали.
+    pub fn normalize(&self, fuel: usize) -> Option<Term> {
+        let mut term = self.clone();
+
+        for _ in 0..fuel {
+            match term.reduce_step() {
+                Some(next) => term = next,
+                None => return Some(term),
+            }
+        }
+
+        match term.reduce_step() {
+            Some(_) => None,
+            None => Some(term),
+        }
+    }
+}
+
+impl LambdaTerm for Term {
+    type Var = Var;
+    type Binder = ();
+
+    fn var(var: Var) -> Self {
+        Term::var(var)
+    }
+
+    fn apply(t1: Term, t2: Term) -> Self {
+        Term::apply(t1, t2)
+    }
+
+    fn lambda(_: (), body: Term) -> Self {
+        Term::lambda(body)
+    }
+
+    fn view(&self) -> View<'_, Self> {
+        match self {
+            Term::Var(x) => View::Var(x),
+            Term::Apply(t1, t2) => View::Apply(t1, t2),
+            Term::Lambda(t) => View::Lambda(&(), t),
+        }
+    }
+
+    fn view_mut(&mut self) -> ViewMut<'_, Self> {
+        match self {
+            Term::Var(x) => ViewMut::Var(x),
+            Term::Apply(t1, t2) => ViewMut::Apply(t1, t2),
+            Term::Lambda(t) => ViewMut::Lambda(&(), t),
+        }
+    }
+
+    fn fmt_binder(_: &(), f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "λ ")
+    }
+}
+
+/// Свързаните променливи се принтират като индекси, а свободните - с
+/// имената си.
+impl Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Var::BVar(i) => write!(f, "{}", i),
+            Var::FVar(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// Формат за принтиране - свързаните променливи се принтират като индекси,
+/// а свободните - с имената си.
+///
+/// Използва се от `println!("{}", ...)`
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lambda::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,106 @@
+use super::{Term, Var};
+use crate::lambda;
+use crate::named::Term as NamedTerm;
+use crate::unnamed::{Strategy, Term as UnnamedTerm};
+use crate::{term, unnamed_term};
+
+fn ln(named: NamedTerm) -> Term {
+    Term::from_named(&named)
+}
+
+#[test]
+fn conversion() {
+    assert_eq!(ln(term!(x)), Term::fvar("x"));
+    assert_eq!(
+        ln(term!(λ x. λ y. x z)),
+        Term::lambda(Term::lambda(Term::apply(Term::bvar(1), Term::fvar("z"))))
+    );
+    assert_eq!(ln(term!(λ x. x y)), ln(term!(λ z. z y)));
+    assert_ne!(ln(term!(λ x. x y)), ln(term!(λ x. x z)));
+
+    assert_eq!(ln(term!(λ x. y (λ y. x y))).to_named(), term!(λ x. y (λ y. x y)));
+    assert_eq!(ln(term!(λ x. λ y. x y)).to_string(), "λ λ 1 0");
+    assert_eq!(ln(term!((λ x. x) y z)).to_string(), "(λ 0) y z");
+}
+
+#[test]
+fn conversion_to_unnamed() {
+    let named = term!(λ x. y (λ w. x w z) y);
+    let (unnamed, names) = UnnamedTerm::from_named(&named);
+
+    let mut context = Vec::new();
+    assert_eq!(ln(named.clone()).to_unnamed(&mut context), unnamed);
+    assert_eq!(context, names);
+    assert_eq!(Term::from_unnamed(&unnamed, &names), ln(named));
+}
+
+#[test]
+fn open_and_close() {
+    let body = Term::apply(Term::bvar(0), Term::lambda(Term::apply(Term::bvar(1), Term::bvar(0))));
+
+    let opened = body.open(&Term::fvar("x"));
+    assert_eq!(opened, Term::apply(Term::fvar("x"), ln(term!(λ y. x y))));
+    assert!(opened.is_locally_closed());
+    assert!(!body.is_locally_closed());
+    assert_eq!(opened.close("x"), body);
+
+    assert_eq!(ln(term!(x y x)).close("x").to_string(), "0 y 0");
+}
+
+#[test]
+fn substitute() {
+    let term = ln(term!(λ y. x y));
+    assert_eq!(term.substitute("x", &Term::fvar("y")), ln(term!(λ z. y z)));
+    assert_eq!(term.substitute("z", &Term::fvar("y")), term);
+    assert_eq!(
+        ln(term!(x (λ x. x))).substitute("x", &ln(term!(λ y. y))),
+        ln(term!((λ y. y) (λ x. x)))
+    );
+}
+
+#[test]
+fn normalize() {
+    let terms = vec![
+        term!((λ x. λ y. x) y z),
+        term!(λ x. (λ y. λ z. y z) x),
+        term!((λ f. λ x. f (f x)) (λ f. λ x. f (f x)) g),
+        term!(λ a. (λ x. λ y. y x) a (λ z. z)),
+    ];
+
+    for term in terms {
+        let (unnamed, names) = UnnamedTerm::from_named(&term);
+        let expected = unnamed.normalize(Strategy::NormalOrder, 100, false).unwrap();
+
+        assert_eq!(
+            ln(term.clone()).normalize(100),
+            Some(Term::from_unnamed(&expected, &names)),
+            "{}",
+            term
+        );
+    }
+
+    assert_eq!(ln(term!((λ x. x x) (λ x. x x))).normalize(100), None);
+    assert_eq!(
+        Term::from_unnamed(&unnamed_term!((λ 0) 0), &[String::from("a")]).normalize(1),
+        Some(Term::fvar("a"))
+    );
+}
+
+#[test]
+fn reduce_under_binders() {
+    let term = ln(term!(λ a. λ b. (λ x. λ y. x b) (a b)));
+    assert_eq!(term.reduce_step(), Some(ln(term!(λ a. λ b. λ y. a b b))));
+
+    let term = ln(term!(λ f. (λ x. λ y. y x) (λ z. f z)));
+    assert_eq!(term.normalize(10), Some(ln(term!(λ f. λ y. y (λ z. f z)))));
+}
+
+#[test]
+fn lambda_term() {
+    let term = ln(term!(λ x. (λ y. y) x z));
+
+    assert_eq!(lambda::size(&term), 7);
+    assert_eq!(lambda::redex_count(&term), 1);
+    assert_eq!(term.to_string(), "λ (λ 0) 0 z");
+    assert_eq!(Var::FVar("z".into()).to_string(), "z");
+}
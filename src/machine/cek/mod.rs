@@ -0,0 +1,143 @@
+use super::{lookup, Stack, Value};
+use crate::unnamed::Term;
+use std::fmt::{self, Display};
+
+/// Текуща задача на CEK машината.
+#[derive(Debug, Clone)]
+pub enum Control<'a> {
+    /// Оценяване на терм в текущата среда.
+    Eval(&'a Term),
+    /// Връщане на вече пресметната стойност към продължението.
+    Return(Value<'a>),
+}
+
+/// Рамка на продължението - какво остава да се направи със стойността,
+/// която се пресмята в момента.
+#[derive(Debug, Clone)]
+pub enum Frame<'a> {
+    /// Стойността е функция - след нея се оценява аргументът в дадената среда.
+    Arg(&'a Term, Stack<Value<'a>>),
+    /// Стойността е аргумент на вече оценената функция.
+    Fun(Value<'a>),
+}
+
+/// Формат за принтиране - `arg ⟨N, [e_0, ..., e_n]⟩` или `fun v`.
+impl<'a> Display for Frame<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Frame::Arg(term, env) => write!(f, "arg ⟨{}, {}⟩", term, env),
+            Frame::Fun(value) => write!(f, "fun {}", value),
+        }
+    }
+}
+
+/// Състояние на CEK машината - управление, среда и продължение.
+///
+/// Машината оценява терма до слаба нормална форма със стратегията извикване
+/// по стойност, като първо оценява функцията, а след това аргумента.
+#[derive(Debug, Clone)]
+pub struct State<'a> {
+    pub control: Control<'a>,
+    pub env: Stack<Value<'a>>,
+    pub cont: Stack<Frame<'a>>,
+}
+
+impl<'a> State<'a> {
+    /// Началното състояние за оценяване на `term` - с празни среда и
+    /// продължение.
+    pub fn new(term: &'a Term) -> Self {
+        State {
+            control: Control::Eval(term),
+            env: Stack::new(),
+            cont: Stack::new(),
+        }
+    }
+
+    /// Извършва един преход на машината.
+    ///
+    /// Връща `None`, ако състоянието е крайно - стойност при празно
+    /// продължение.
+    pub fn step(&self) -> Option<State<'a>> {
+        let state = match &self.control {
+            Control::Eval(Term::Var(i)) => State {
+                control: Control::Return(lookup(&self.env, *i)),
+                env: self.env.clone(),
+                cont: self.cont.clone(),
+            },
+            Control::Eval(term @ Term::Lambda(_)) => State {
+                control: Control::Return(Value::Closure(term, self.env.clone())),
+                env: self.env.clone(),
+                cont: self.cont.clone(),
+            },
+            Control::Eval(Term::Apply(t1, t2)) => State {
+                control: Control::Eval(t1),
+                env: self.env.clone(),
+                cont: self.cont.push(Frame::Arg(t2, self.env.clone())),
+            },
+            Control::Return(value) => {
+                let (frame, cont) = self.cont.pop()?;
+
+                match frame {
+                    Frame::Arg(term, env) => State {
+                        control: Control::Eval(term),
+                        env: env.clone(),
+                        cont: cont.push(Frame::Fun(value.clone())),
+                    },
+                    Frame::Fun(Value::Closure(Term::Lambda(body), env)) => State {
+                        control: Control::Eval(body),
+                        env: env.push(value.clone()),
+                        cont,
+                    },
+                    Frame::Fun(fun) => State {
+                        control: Control::Return(fun.apply_neutral(value)?),
+                        env: self.env.clone(),
+                        cont,
+                    },
+                }
+            },
+        };
+
+        Some(state)
+    }
+
+    /// Връща пресметнатата стойност, ако състоянието е крайно.
+    pub fn value(&self) -> Option<&Value<'a>> {
+        match &self.control {
+            Control::Return(value) if self.cont.is_empty() => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Формат за принтиране - `eval M | [e_0, ..., e_n] | [k_0, ..., k_m]` или
+/// `ret v | [k_0, ..., k_m]`.
+impl<'a> Display for State<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.control {
+            Control::Eval(term) => write!(f, "eval {} | {} | {}", term, self.env, self.cont),
+            Control::Return(value) => write!(f, "ret {} | {}", value, self.cont),
+        }
+    }
+}
+
+/// Връща всички състояния на машината при оценяването на `term`, като
+/// започва от началното.
+///
+/// Ако оценяването не завършва, редицата е безкрайна.
+pub fn trace(term: &Term) -> impl Iterator<Item = State<'_>> {
+    std::iter::successors(Some(State::new(term)), State::step)
+}
+
+/// Оценява `term` до слаба нормална форма, като прави не повече от `fuel`
+/// прехода.
+///
+/// Връща `None`, ако преходите не са стигнали.
+pub fn evaluate(term: &Term, fuel: usize) -> Option<Term> {
+    trace(term)
+        .take(fuel + 1)
+        .last()
+        .and_then(|state| state.value().map(Value::to_term))
+}
+
+#[cfg(test)]
+mod tests;
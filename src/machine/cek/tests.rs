@@ -0,0 +1,46 @@
+use super::*;
+use crate::fixtures;
+use crate::unnamed::Strategy;
+use crate::unnamed_term;
+
+#[test]
+fn step() {
+    let term = unnamed_term!((λ 0) (λ 0 0));
+    let states = trace(&term).map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        states,
+        vec![
+            "eval (λ 0) (λ 0 0) | [] | []",
+            "eval λ 0 | [] | [arg ⟨λ 0 0, []⟩]",
+            "ret ⟨λ 0, []⟩ | [arg ⟨λ 0 0, []⟩]",
+            "eval λ 0 0 | [] | [fun ⟨λ 0, []⟩]",
+            "ret ⟨λ 0 0, []⟩ | [fun ⟨λ 0, []⟩]",
+            "eval 0 | [⟨λ 0 0, []⟩] | []",
+            "ret ⟨λ 0 0, []⟩ | []",
+        ]
+    );
+}
+
+#[test]
+fn evaluate() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+
+    assert_eq!(super::evaluate(&unnamed_term!(0 1), 10), Some(unnamed_term!(0 1)));
+    assert_eq!(super::evaluate(&unnamed_term!((λ 0) 1), 10), Some(unnamed_term!(1)));
+    assert_eq!(super::evaluate(&unnamed_term!((λ 0) 1), 4), None);
+    assert_eq!(super::evaluate(&omega, 100), None);
+    assert_eq!(super::evaluate(&unnamed_term!((λ λ 0) ((λ 0 0) (λ 0 0))), 100), None);
+}
+
+#[test]
+fn agrees_with_call_by_value() {
+    for term in fixtures::sample_terms() {
+        assert_eq!(
+            super::evaluate(&term, 1000),
+            term.normalize(Strategy::CallByValue, 1000, false),
+            "{}",
+            term
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::{substitute_env, Stack};
+use crate::unnamed::Term;
+use std::fmt::{self, Display};
+
+/// Затваряне - терм заедно със средата, в която се оценява.
+///
+/// Променливата с индекс `i` в терма се отнася за `i`-тия елемент на
+/// средата, ако е свободна спрямо абстракциите в терма.
+#[derive(Debug, Clone)]
+pub struct Closure<'a> {
+    pub term: &'a Term,
+    pub env: Stack<Closure<'a>>,
+}
+
+impl<'a> Closure<'a> {
+    pub fn new(term: &'a Term, env: Stack<Closure<'a>>) -> Self {
+        Closure { term, env }
+    }
+
+    /// Превръща затварянето обратно в терм, като замества променливите от
+    /// средата със съответните им термове.
+    ///
+    /// Свободните променливи, които не са в средата, остават свободни
+    /// променливи на резултата.
+    pub fn to_term(&self) -> Term {
+        substitute_env(self.term, &self.env, &Closure::to_term)
+    }
+}
+
+/// Формат за принтиране - `⟨M, [e_0, ..., e_n]⟩`.
+impl<'a> Display for Closure<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "⟨{}, {}⟩", self.term, self.env)
+    }
+}
+
+/// Състояние на машината на Кривин - текущ терм, среда и стек от аргументи.
+///
+/// Машината оценява терма до слаба глава нормална форма със стратегията
+/// извикване по име. Аргументите не се оценяват, а се слагат в стека като
+/// затваряния.
+#[derive(Debug, Clone)]
+pub struct State<'a> {
+    pub closure: Closure<'a>,
+    pub stack: Stack<Closure<'a>>,
+}
+
+impl<'a> State<'a> {
+    /// Началното състояние за оценяване на `term` - с празни среда и стек.
+    pub fn new(term: &'a Term) -> Self {
+        State {
+            closure: Closure::new(term, Stack::new()),
+            stack: Stack::new(),
+        }
+    }
+
+    /// Извършва един преход на машината.
+    ///
+    /// Връща `None`, ако състоянието е крайно - абстракция при празен стек
+    /// или свободна променлива.
+    pub fn step(&self) -> Option<State<'a>> {
+        let env = &self.closure.env;
+
+        match self.closure.term {
+            &Term::Var(i) => env.get(i).map(|closure| State {
+                closure: closure.clone(),
+                stack: self.stack.clone(),
+            }),
+            Term::Apply(t1, t2) => Some(State {
+                closure: Closure::new(t1, env.clone()),
+                stack: self.stack.push(Closure::new(t2, env.clone())),
+            }),
+            Term::Lambda(t) => self.stack.pop().map(|(arg, stack)| State {
+                closure: Closure::new(t, env.push(arg.clone())),
+                stack,
+            }),
+        }
+    }
+
+    /// Превръща състоянието в терм - текущото затваряне, приложено към
+    /// аргументите в стека.
+    pub fn to_term(&self) -> Term {
+        self.stack
+            .iter()
+            .fold(self.closure.to_term(), |t, arg| Term::apply(t, arg.to_term()))
+    }
+}
+
+/// Формат за принтиране - `⟨M, [e_0, ..., e_n]⟩ | [s_0, ..., s_m]`.
+impl<'a> Display for State<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} | {}", self.closure, self.stack)
+    }
+}
+
+/// Връща всички състояния на машината при оценяването на `term`, като
+/// започва от началното.
+///
+/// Ако термът няма слаба глава нормална форма, редицата е безкрайна.
+pub fn trace(term: &Term) -> impl Iterator<Item = State<'_>> {
+    std::iter::successors(Some(State::new(term)), State::step)
+}
+
+/// Оценява `term` до слаба глава нормална форма, като прави не повече от
+/// `fuel` прехода.
+///
+/// Връща `None`, ако преходите не са стигнали.
+pub fn evaluate(term: &Term, fuel: usize) -> Option<Term> {
+    trace(term)
+        .take(fuel + 1)
+        .last()
+        .filter(|state| state.step().is_none())
+        .map(|state| state.to_term())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,63 @@
+use super::*;
+use crate::fixtures;
+use crate::unnamed::Strategy;
+use crate::unnamed_term;
+
+#[test]
+fn step() {
+    let term = unnamed_term!((λ 0) (λ 0 0));
+    let states = trace(&term).map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        states,
+        vec![
+            "⟨(λ 0) (λ 0 0), []⟩ | []",
+            "⟨λ 0, []⟩ | [⟨λ 0 0, []⟩]",
+            "⟨0, [⟨λ 0 0, []⟩]⟩ | []",
+            "⟨λ 0 0, []⟩ | []",
+        ]
+    );
+}
+
+#[test]
+fn to_term() {
+    let term = unnamed_term!(λ 1 2);
+    let var = unnamed_term!(0);
+    let env = Stack::new()
+        .push(Closure::new(&var, Stack::new()))
+        .push(Closure::new(&term, Stack::new()));
+    let arg = unnamed_term!(λ 0 1);
+
+    assert_eq!(
+        Closure::new(&term, env.clone()).to_term(),
+        unnamed_term!(λ (λ 2 3) 1)
+    );
+
+    let state = State {
+        closure: Closure::new(&term, env.clone()),
+        stack: Stack::new().push(Closure::new(&arg, env)),
+    };
+    assert_eq!(state.to_term(), unnamed_term!((λ (λ 2 3) 1) (λ 0 (λ 2 3))));
+}
+
+#[test]
+fn evaluate() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+
+    assert_eq!(super::evaluate(&unnamed_term!(0 1), 10), Some(unnamed_term!(0 1)));
+    assert_eq!(super::evaluate(&unnamed_term!((λ 0) 1), 10), Some(unnamed_term!(1)));
+    assert_eq!(super::evaluate(&omega, 100), None);
+    assert_eq!(super::evaluate(&unnamed_term!((λ 0) 1), 2), None);
+}
+
+#[test]
+fn agrees_with_call_by_name() {
+    for term in fixtures::sample_terms() {
+        assert_eq!(
+            super::evaluate(&term, 1000),
+            term.normalize(Strategy::CallByName, 1000, false),
+            "{}",
+            term
+        );
+    }
+}
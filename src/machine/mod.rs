@@ -0,0 +1,169 @@
+pub mod cek;
+pub mod krivine;
+pub mod secd;
+
+use crate::unnamed::Term;
+use std::fmt::{self, Display};
+use std::rc::Rc;
+
+/// Неизменяем стек, реализиран като свързан списък с общи опашки.
+///
+/// Използва се за средите и стековете на абстрактните машини. Добавянето на
+/// елемент не променя стария стек, а копирането е с константна сложност,
+/// така че всяко състояние на машината може да бъде запазено за преглед.
+#[derive(Debug)]
+pub struct Stack<T>(Option<Rc<Node<T>>>);
+
+#[derive(Debug)]
+struct Node<T> {
+    head: T,
+    tail: Stack<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack(None)
+    }
+
+    /// Връща нов стек с `value` на върха.
+    pub fn push(&self, value: T) -> Self {
+        Stack(Some(Rc::new(Node {
+            head: value,
+            tail: self.clone(),
+        })))
+    }
+
+    /// Връща елемента на върха и стека без него.
+    pub fn pop(&self) -> Option<(&T, Stack<T>)> {
+        self.0.as_ref().map(|node| (&node.head, node.tail.clone()))
+    }
+
+    /// Връща елемента на позиция `index`, като върхът е на позиция 0.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Обхожда елементите от върха към дъното.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut current = &self.0;
+
+        std::iter::from_fn(move || {
+            current.as_ref().map(|node| {
+                current = &node.tail.0;
+                &node.head
+            })
+        })
+    }
+}
+
+impl<T> Clone for Stack<T> {
+    fn clone(&self) -> Self {
+        Stack(self.0.clone())
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+/// Формат за принтиране - `[x_0, x_1, ..., x_n]`, като `x_0` е върхът.
+impl<T: Display> Display for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+/// Стойност при оценяване със стратегията извикване по стойност.
+#[derive(Debug, Clone)]
+pub enum Value<'a> {
+    /// Затваряне - абстракция `λ M` заедно със средата, в която се оценява.
+    Closure(&'a Term, Stack<Value<'a>>),
+    /// Терм, чиято глава е свободна променлива. Не може да бъде приложен,
+    /// затова прилагането му към аргумент също дава неутрална стойност.
+    Neutral(Term),
+}
+
+impl<'a> Value<'a> {
+    /// Превръща стойността обратно в терм, като замества променливите от
+    /// средата на затварянето със съответните им стойности.
+    pub fn to_term(&self) -> Term {
+        match self {
+            Value::Closure(term, env) => substitute_env(term, env, &Value::to_term),
+            Value::Neutral(term) => term.clone(),
+        }
+    }
+
+    /// Прилага стойността към аргумент, ако е неутрална.
+    fn apply_neutral(&self, arg: &Value) -> Option<Value<'a>> {
+        match self {
+            Value::Closure(_, _) => None,
+            Value::Neutral(term) => Some(Value::Neutral(Term::apply(term.clone(), arg.to_term()))),
+        }
+    }
+}
+
+/// Формат за принтиране - `⟨λ M, [e_0, ..., e_n]⟩` или неутралния терм.
+impl<'a> Display for Value<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Closure(term, env) => write!(f, "⟨{}, {}⟩", term, env),
+            Value::Neutral(term) => write!(f, "{}", term),
+        }
+    }
+}
+
+/// Връща стойността на променливата с индекс `i` в средата `env`.
+///
+/// Ако променливата не е в средата, тя е свободна и стойността е неутрална.
+fn lookup<'a>(env: &Stack<Value<'a>>, i: usize) -> Value<'a> {
+    match env.get(i) {
+        Some(value) => value.clone(),
+        None => Value::Neutral(Term::var(i - env.len())),
+    }
+}
+
+/// Замества променливите от средата `env` в `term`, като `read` превръща
+/// елементите на средата в термове.
+///
+/// Индексите на свободните променливи, които не са в средата, се намаляват с
+/// дължината на средата.
+fn substitute_env<T>(term: &Term, env: &Stack<T>, read: &dyn Fn(&T) -> Term) -> Term {
+    fn inner<T>(term: &Term, env: &Stack<T>, read: &dyn Fn(&T) -> Term, depth: usize) -> Term {
+        match term {
+            &Term::Var(i) if i < depth => Term::var(i),
+            &Term::Var(i) => match env.get(i - depth) {
+                Some(value) => (0..depth).fold(read(value), |t, _| t.raise(0)),
+                None => Term::var(i - env.len()),
+            },
+            Term::Apply(t1, t2) => Term::apply(
+                inner(t1, env, read, depth),
+                inner(t2, env, read, depth),
+            ),
+            Term::Lambda(t) => Term::lambda(inner(t, env, read, depth + 1)),
+        }
+    }
+
+    inner(term, env, read, 0)
+}
+
+#[cfg(test)]
+mod tests;
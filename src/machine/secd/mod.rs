@@ -0,0 +1,170 @@
+use super::{lookup, Stack, Value};
+use crate::unnamed::Term;
+use std::fmt::{self, Display};
+
+/// Инструкция в управляващия списък на SECD машината.
+#[derive(Debug, Clone)]
+pub enum Instruction<'a> {
+    /// Оценяване на терм и слагане на стойността му в стека.
+    Term(&'a Term),
+    /// Прилагане на функцията под върха на стека към аргумента на върха.
+    Apply,
+}
+
+/// Формат за принтиране - термът или `ap`.
+impl<'a> Display for Instruction<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instruction::Term(term) => write!(f, "{}", term),
+            Instruction::Apply => write!(f, "ap"),
+        }
+    }
+}
+
+/// Запазено състояние, към което машината се връща след края на
+/// прилагането на функция.
+#[derive(Debug, Clone)]
+pub struct Dump<'a> {
+    pub stack: Stack<Value<'a>>,
+    pub env: Stack<Value<'a>>,
+    pub control: Stack<Instruction<'a>>,
+}
+
+/// Формат за принтиране - `(S, E, C)`.
+impl<'a> Display for Dump<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.stack, self.env, self.control)
+    }
+}
+
+/// Състояние на SECD машината - стек, среда, управление и дъмп.
+///
+/// Машината оценява терма до слаба нормална форма със стратегията извикване
+/// по стойност, като първо оценява функцията, а след това аргумента.
+#[derive(Debug, Clone)]
+pub struct State<'a> {
+    pub stack: Stack<Value<'a>>,
+    pub env: Stack<Value<'a>>,
+    pub control: Stack<Instruction<'a>>,
+    pub dump: Stack<Dump<'a>>,
+}
+
+impl<'a> State<'a> {
+    /// Началното състояние за оценяване на `term` - управлението съдържа
+    /// само `term`, а останалите компоненти са празни.
+    pub fn new(term: &'a Term) -> Self {
+        State {
+            stack: Stack::new(),
+            env: Stack::new(),
+            control: Stack::new().push(Instruction::Term(term)),
+            dump: Stack::new(),
+        }
+    }
+
+    /// Извършва един преход на машината.
+    ///
+    /// Връща `None`, ако състоянието е крайно - празни управление и дъмп.
+    pub fn step(&self) -> Option<State<'a>> {
+        let (instruction, control) = match self.control.pop() {
+            Some(next) => next,
+            None => {
+                let (dump, rest) = self.dump.pop()?;
+                let (value, _) = self.stack.pop()?;
+
+                return Some(State {
+                    stack: dump.stack.push(value.clone()),
+                    env: dump.env.clone(),
+                    control: dump.control.clone(),
+                    dump: rest,
+                });
+            },
+        };
+
+        let state = match instruction {
+            Instruction::Term(Term::Var(i)) => State {
+                stack: self.stack.push(lookup(&self.env, *i)),
+                env: self.env.clone(),
+                control,
+                dump: self.dump.clone(),
+            },
+            Instruction::Term(term @ Term::Lambda(_)) => State {
+                stack: self.stack.push(Value::Closure(term, self.env.clone())),
+                env: self.env.clone(),
+                control,
+                dump: self.dump.clone(),
+            },
+            Instruction::Term(Term::Apply(t1, t2)) => State {
+                stack: self.stack.clone(),
+                env: self.env.clone(),
+                control: control
+                    .push(Instruction::Apply)
+                    .push(Instruction::Term(t2))
+                    .push(Instruction::Term(t1)),
+                dump: self.dump.clone(),
+            },
+            Instruction::Apply => {
+                let (arg, stack) = self.stack.pop()?;
+                let (fun, stack) = stack.pop()?;
+
+                match fun {
+                    Value::Closure(Term::Lambda(body), env) => State {
+                        stack: Stack::new(),
+                        env: env.push(arg.clone()),
+                        control: Stack::new().push(Instruction::Term(body)),
+                        dump: self.dump.push(Dump {
+                            stack,
+                            env: self.env.clone(),
+                            control,
+                        }),
+                    },
+                    _ => State {
+                        stack: stack.push(fun.apply_neutral(arg)?),
+                        env: self.env.clone(),
+                        control,
+                        dump: self.dump.clone(),
+                    },
+                }
+            },
+        };
+
+        Some(state)
+    }
+
+    /// Връща пресметнатата стойност, ако състоянието е крайно.
+    pub fn value(&self) -> Option<&Value<'a>> {
+        if self.control.is_empty() && self.dump.is_empty() {
+            self.stack.get(0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Формат за принтиране - `S | E | C | D`.
+impl<'a> Display for State<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} | {} | {} | {}", self.stack, self.env, self.control, self.dump)
+    }
+}
+
+/// Връща всички състояния на машината при оценяването на `term`, като
+/// започва от началното.
+///
+/// Ако оценяването не завършва, редицата е безкрайна.
+pub fn trace(term: &Term) -> impl Iterator<Item = State<'_>> {
+    std::iter::successors(Some(State::new(term)), State::step)
+}
+
+/// Оценява `term` до слаба нормална форма, като прави не повече от `fuel`
+/// прехода.
+///
+/// Връща `None`, ако преходите не са стигнали.
+pub fn evaluate(term: &Term, fuel: usize) -> Option<Term> {
+    trace(term)
+        .take(fuel + 1)
+        .last()
+        .and_then(|state| state.value().map(Value::to_term))
+}
+
+#[cfg(test)]
+mod tests;
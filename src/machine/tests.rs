@@ -0,0 +1,23 @@
+use super::Stack;
+
+#[test]
+fn stack() {
+    let empty = Stack::new();
+    let stack = empty.push(1).push(2).push(3);
+
+    assert!(empty.is_empty());
+    assert_eq!(stack.len(), 3);
+    assert_eq!(stack.get(0), Some(&3));
+    assert_eq!(stack.get(2), Some(&1));
+    assert_eq!(stack.get(3), None);
+    assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+    let (top, rest) = stack.pop().unwrap();
+    assert_eq!(*top, 3);
+    assert_eq!(rest.len(), 2);
+    assert_eq!(stack.len(), 3);
+    assert!(empty.pop().is_none());
+
+    assert_eq!(stack.to_string(), "[3, 2, 1]");
+    assert_eq!(empty.to_string(), "[]");
+}
@@ -1,8 +1,25 @@
+mod debugger;
+
+use debugger::Debugger;
+use hw::enumeration::{self, Measure};
+use hw::machine::{cek, krivine, secd};
 use hw::named::Term as NamedTerm;
-use hw::unnamed::Term as UnnamedTerm;
+use hw::nbe;
+use hw::optimal;
+use hw::parallel;
+use hw::sigma::Term as SigmaTerm;
+use hw::unnamed::{Convertibility, Statistics, Strategy, Term as UnnamedTerm};
 
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::ThreadPoolBuilder;
 use ron::de;
+use serde::de::DeserializeOwned;
+use std::fmt::Display;
 use std::io::stdin;
+use std::panic;
+use std::str::FromStr;
+use std::thread;
 use structopt::StructOpt;
 
 /// Интерактивна демонстрация на функционалността.
@@ -88,9 +105,377 @@ enum Command {
     /// ```
     #[structopt(name = "conv-unnamed")]
     ConvertUnnamed,
+
+    /// Търси общ редукт на два именувани терма
+    ///
+    /// Приема два агумента от стандартния вход, всеки на нов ред. Ако в рамките
+    /// на `fuel` стъпки за всеки терм бъде намерен общ редукт, извежда двете
+    /// редукционни редици до него.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Apply(Apply(Lambda("x", Var("x")), Var("a")), Apply(Lambda("x", Var("x")), Var("a")))
+    /// Apply(Lambda("x", Apply(Var("x"), Var("x"))), Var("a"))
+    /// ```
+    ///
+    /// Изход
+    /// ```
+    /// (λ x. x) a ((λ x. x) a) → a ((λ x. x) a) → a a
+    /// (λ x. x x) a → a a
+    /// ```
+    #[structopt(name = "confluence")]
+    Confluence {
+        /// Максимален брой стъпки за всеки от термовете
+        #[structopt(long = "fuel", default_value = "20")]
+        fuel: usize,
+    },
+
+    /// Проверява дали два именувани терма са конвертируеми
+    ///
+    /// Приема два агумента от стандартния вход, всеки на нов ред. Двата терма
+    /// се нормализират с нормалната стратегия и нормалните им форми се
+    /// сравняват.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Lambda("x", Apply(Var("f"), Var("x")))
+    /// Var("f")
+    /// ```
+    ///
+    /// Изход (с `--eta`)
+    /// ```
+    /// λ x. f x =βη f
+    /// ```
+    #[structopt(name = "equiv")]
+    Equivalent {
+        /// Максимален брой стъпки за нормализиране на всеки от термовете
+        #[structopt(long = "fuel", default_value = "1000")]
+        fuel: usize,
+
+        /// Проверява за βη-конвертируемост вместо за β-конвертируемост
+        #[structopt(long = "eta")]
+        eta: bool,
+    },
+
+    /// Оценява именуван терм с абстрактна машина
+    ///
+    /// Приема един аргумент от стандартния вход. Машината на Кривин оценява
+    /// със стратегията извикване по име, а CEK и SECD - със стратегията
+    /// извикване по стойност. И трите спират в слаба глава нормална форма.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Apply(Lambda("x", Var("x")), Lambda("y", Var("y")))
+    /// ```
+    ///
+    /// Изход (с `--kind secd --trace`)
+    /// ```
+    /// [] | [] | [(λ 0) (λ 0)] | []
+    /// [] | [] | [λ 0, λ 0, ap] | []
+    /// [⟨λ 0, []⟩] | [] | [λ 0, ap] | []
+    /// [⟨λ 0, []⟩, ⟨λ 0, []⟩] | [] | [ap] | []
+    /// [] | [⟨λ 0, []⟩] | [0] | [([], [], [])]
+    /// [⟨λ 0, []⟩] | [⟨λ 0, []⟩] | [] | [([], [], [])]
+    /// [⟨λ 0, []⟩] | [] | [] | []
+    /// λ x. x
+    /// ```
+    #[structopt(name = "machine")]
+    Machine {
+        /// Вид на машината - krivine, cek или secd
+        #[structopt(long = "kind", default_value = "cek")]
+        kind: MachineKind,
+
+        /// Извежда всяко състояние на машината
+        #[structopt(long = "trace")]
+        trace: bool,
+
+        /// Максимален брой преходи
+        #[structopt(long = "fuel", default_value = "10000")]
+        fuel: usize,
+    },
+
+    /// Намира нормалната форма на именуван терм
+    ///
+    /// Приема един аргумент от стандартния вход. Бекендът `naive` редуцира с
+    /// нормалната стратегия чрез субституция, `nbe` - чрез нормализация
+    /// чрез оценяване, която е значително по-бърза за големи термове, а
+    /// `optimal` - с експерименталната оптимална редукция на Лампинг, като
+    /// извежда и броя на β-стъпките и на всички взаимодействия. Бекендът
+    /// `parallel` свива независимите редекси едновременно в `--jobs` нишки.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Apply(Lambda("x", Apply(Var("x"), Var("x"))), Lambda("y", Var("y")))
+    /// ```
+    ///
+    /// Изход
+    /// ```
+    /// λ x. x
+    /// ```
+    #[structopt(name = "normalize")]
+    Normalize {
+        /// Начин на нормализиране - naive, nbe, optimal или parallel
+        #[structopt(long = "backend", default_value = "nbe")]
+        backend: Backend,
+
+        /// Максимален брой β-стъпки (взаимодействия за optimal, рундове за
+        /// parallel)
+        #[structopt(long = "fuel", default_value = "100000")]
+        fuel: usize,
+
+        /// Брой нишки за parallel (0 - по една за всяко ядро)
+        #[structopt(long = "jobs", default_value = "0")]
+        jobs: usize,
+    },
+
+    /// Редуцира именуван терм в λσ-смятането с явни субституции
+    ///
+    /// Приема един аргумент от стандартния вход и принтира всяка стъпка
+    /// заедно с приложеното правило, а накрая - нормалната форма.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Apply(Lambda("x", Lambda("y", Var("x"))), Var("z"))
+    /// ```
+    ///
+    /// Изход
+    /// ```
+    /// (λ λ 1) 0
+    /// Beta: (λ 1)[0 · id]
+    /// Abs: λ 1[0 · ((0 · id) ∘ ↑)]
+    /// VarSkip: λ 0[(0 · id) ∘ ↑]
+    /// Map: λ 0[0[↑] · (id ∘ ↑)]
+    /// VarCons: λ 0[↑]
+    /// VarShift: λ 1
+    /// λ x. z
+    /// ```
+    #[structopt(name = "sigma")]
+    Sigma {
+        /// Максимален брой стъпки
+        #[structopt(long = "fuel", default_value = "1000")]
+        fuel: usize,
+    },
+
+    /// Интерактивно редуциране на именуван терм стъпка по стъпка
+    ///
+    /// Приема терм на първия ред от стандартния вход, а на следващите
+    /// редове - команди. Командата `help` показва всички команди.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Apply(Lambda("x", Var("x")), Apply(Lambda("y", Var("y")), Var("z")))
+    /// redexes
+    /// contract 1
+    /// undo
+    /// strategy value
+    /// step
+    /// ```
+    ///
+    /// Изход
+    /// ```
+    /// [0] (λ x. x) ((λ x. x) z)
+    /// > [(λ 0) [(λ 0) 0]₁]₀
+    /// > [1] (λ x. x) z
+    /// > [0] (λ x. x) ((λ x. x) z)
+    /// > value
+    /// > [1] (λ x. x) z
+    /// >
+    /// ```
+    #[structopt(name = "debug")]
+    Debug {
+        /// Максимален брой стъпки за командата `run`
+        #[structopt(long = "fuel", default_value = "10000")]
+        fuel: usize,
+    },
+
+    /// Сравнява стратегиите за редукция върху именуван терм
+    ///
+    /// Приема един аргумент от стандартния вход, нормализира го с всяка
+    /// стратегия и принтира таблица с броя на β-стъпките, най-големия размер
+    /// на междинен терм, общата работа по субституциите и дали редукцията е
+    /// завършила в рамките на `--fuel` стъпки.
+    ///
+    /// # Пример
+    ///
+    /// Вход
+    /// ```
+    /// Apply(Lambda("x", Apply(Var("x"), Var("x"))), Apply(Lambda("y", Var("y")), Var("z")))
+    /// ```
+    ///
+    /// Изход
+    /// ```
+    /// стратегия      стъпки   макс. размер  субституции  завършила
+    /// normal              3              9           15         да
+    /// applicative         2              9            7         да
+    /// name                2              9           13         да
+    /// value               2              9            7         да
+    /// ```
+    #[structopt(name = "compare")]
+    Compare {
+        /// Максимален брой β-стъпки за всяка стратегия
+        #[structopt(long = "fuel", default_value = "1000")]
+        fuel: usize,
+    },
+
+    /// Генерира равномерно случайни затворени термове с даден размер
+    ///
+    /// Не приема аргументи от стандартния вход. Всички затворени термове с
+    /// размер `--size` са еднакво вероятни. Термовете се извеждат в
+    /// именувана нотация, по един на ред.
+    ///
+    /// # Пример
+    ///
+    /// Изход (с `--size 6 --count 3 --seed 1`)
+    /// ```
+    /// λ x. λ y. λ z. x y
+    /// λ x. x (λ y. λ z. y)
+    /// λ x. λ y. λ z. x x
+    /// ```
+    #[structopt(name = "random")]
+    Random {
+        /// Размер на термовете
+        #[structopt(long = "size", default_value = "20")]
+        size: usize,
+
+        /// Мярка за размер - nodes (брой върхове) или binary (дължина на
+        /// двоичния запис)
+        #[structopt(long = "measure", default_value = "nodes")]
+        measure: Measure,
+
+        /// Брой термове
+        #[structopt(long = "count", default_value = "1")]
+        count: usize,
+
+        /// Начална стойност на генератора на случайни числа
+        #[structopt(long = "seed")]
+        seed: Option<u64>,
+    },
+}
+
+/// Абстрактна машина за командата `machine`.
+enum MachineKind {
+    Krivine,
+    Cek,
+    Secd,
+}
+
+impl FromStr for MachineKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "krivine" => Ok(MachineKind::Krivine),
+            "cek" => Ok(MachineKind::Cek),
+            "secd" => Ok(MachineKind::Secd),
+            _ => Err(format!("непозната машина '{}'", s)),
+        }
+    }
+}
+
+/// Прочита един ред от стандартния вход в RON формат.
+fn read<T: DeserializeOwned>() -> T {
+    let mut line = String::new();
+    stdin().read_line(&mut line).unwrap();
+    parse(&line)
+}
+
+/// Разбира низ в RON формат.
+fn parse<T: DeserializeOwned>(text: &str) -> T {
+    de::from_str::<T>(text).expect("parse error")
+}
+
+/// Размер на стека на нишката, в която се изпълнява командата.
+///
+/// Десериализацията на термовете и някои от бекендите са рекурсивни, така че
+/// с голям стек програмата работи и за много дълбоки термове.
+const STACK_SIZE: usize = 1 << 30;
+
+/// Изпълнява `f` в нова нишка със стек с размер `STACK_SIZE`.
+fn with_large_stack<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(f)
+        .expect("thread error")
+        .join()
+        .unwrap_or_else(|e| panic::resume_unwind(e))
+}
+
+/// Начин на нормализиране за командата `normalize`.
+enum Backend {
+    Naive,
+    Nbe,
+    Optimal,
+    Parallel,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "naive" => Ok(Backend::Naive),
+            "nbe" => Ok(Backend::Nbe),
+            "optimal" => Ok(Backend::Optimal),
+            "parallel" => Ok(Backend::Parallel),
+            _ => Err(format!("непознат бекенд '{}'", s)),
+        }
+    }
+}
+
+/// Изпълнява абстрактна машина, като обхожда състоянията ѝ `states`.
+///
+/// Извежда всяко състояние, ако `trace` е `true`, и накрая резултата в
+/// именувана нотация, ако машината е спряла в рамките на `fuel` прехода.
+fn run_machine<S, I, F>(states: I, fuel: usize, trace: bool, result: F)
+where
+    S: Display,
+    I: Iterator<Item = S>,
+    F: Fn(&S) -> Option<UnnamedTerm>,
+{
+    let mut last = None;
+
+    for state in states.take(fuel + 1) {
+        if trace {
+            println!("{}", state);
+        }
+        last = Some(state);
+    }
+
+    match last.as_ref().and_then(result) {
+        Some(term) => println!("{}", NamedTerm::from_unnamed(&term)),
+        None => println!("машината не е спряла за {} прехода", fuel),
+    }
+}
+
+/// Форматира редукционна редица в именувана нотация.
+fn format_path(path: &[UnnamedTerm]) -> String {
+    path.iter()
+        .map(|term| NamedTerm::from_unnamed(term).to_string())
+        .collect::<Vec<_>>()
+        .join(" → ")
 }
 
 fn main() {
+    with_large_stack(run);
+}
+
+fn run() {
     let cmd = Command::from_args();
 
     match cmd {
@@ -150,5 +535,141 @@ fn main() {
             let term = de::from_str::<UnnamedTerm>(&line).expect("parse error");
             println!("{}", NamedTerm::from_unnamed(&term));
         },
+        Command::Confluence { fuel } => {
+            let mut names = Vec::new();
+            let left = UnnamedTerm::from_named_with(&read::<NamedTerm>(), &mut names);
+            let right = UnnamedTerm::from_named_with(&read::<NamedTerm>(), &mut names);
+
+            match left.common_reduct(&right, fuel) {
+                Some((left_path, right_path)) => {
+                    println!("{}", format_path(&left_path));
+                    println!("{}", format_path(&right_path));
+                },
+                None => println!("не е намерен общ редукт за {} стъпки", fuel),
+            }
+        },
+        Command::Equivalent { fuel, eta } => {
+            let left = read::<NamedTerm>();
+            let right = read::<NamedTerm>();
+            let relation = if eta { "βη" } else { "β" };
+
+            match left.convertible(&right, fuel, eta) {
+                Convertibility::Equal => println!("{} ={} {}", left, relation, right),
+                Convertibility::DifferentNormalForms => {
+                    println!("{} ≠{} {}", left, relation, right)
+                },
+                Convertibility::Undecided => {
+                    println!("не е достигната нормална форма за {} стъпки", fuel)
+                },
+            }
+        },
+        Command::Machine { kind, trace, fuel } => {
+            let term = UnnamedTerm::from_named(&read::<NamedTerm>()).0;
+
+            match kind {
+                MachineKind::Krivine => run_machine(krivine::trace(&term), fuel, trace, |s| {
+                    s.step().map_or(Some(s.to_term()), |_| None)
+                }),
+                MachineKind::Cek => run_machine(cek::trace(&term), fuel, trace, |s| {
+                    s.value().map(|v| v.to_term())
+                }),
+                MachineKind::Secd => run_machine(secd::trace(&term), fuel, trace, |s| {
+                    s.value().map(|v| v.to_term())
+                }),
+            }
+        },
+        Command::Normalize { backend, fuel, jobs } => {
+            let term = UnnamedTerm::from_named(&read::<NamedTerm>()).0;
+
+            let normal_form = match backend {
+                Backend::Naive => term.normalize(Strategy::NormalOrder, fuel, false),
+                Backend::Nbe => nbe::normalize(&term, fuel),
+                Backend::Optimal => optimal::normalize(&term, fuel).map(|(t, interactions)| {
+                    println!(
+                        "β-стъпки: {}, взаимодействия: {}",
+                        interactions.beta, interactions.total
+                    );
+                    t
+                }),
+                Backend::Parallel => ThreadPoolBuilder::new()
+                    .num_threads(jobs)
+                    .stack_size(STACK_SIZE)
+                    .build()
+                    .expect("thread pool error")
+                    .install(|| parallel::normalize(&term, fuel)),
+            };
+
+            match normal_form {
+                Some(term) => println!("{}", NamedTerm::from_unnamed(&term)),
+                None => println!("не е достигната нормална форма за {} стъпки", fuel),
+            }
+        },
+        Command::Sigma { fuel } => {
+            let (term, names) = UnnamedTerm::from_named(&read::<NamedTerm>());
+            let term = SigmaTerm::from_unnamed(&term);
+            println!("{}", term);
+
+            let mut last = term.clone();
+            for (rule, next) in term.trace().take(fuel) {
+                println!("{}: {}", rule, next);
+                last = next;
+            }
+
+            match last.step().map_or_else(|| last.to_unnamed(), |_| None) {
+                Some(term) => println!("{}", NamedTerm::from_unnamed_with(&term, &names)),
+                None => println!("не е достигната нормална форма за {} стъпки", fuel),
+            }
+        },
+        Command::Debug { fuel } => {
+            let (term, names) = UnnamedTerm::from_named(&read::<NamedTerm>());
+            Debugger::new(term, names, fuel).run();
+        },
+        Command::Compare { fuel } => {
+            let term = UnnamedTerm::from_named(&read::<NamedTerm>()).0;
+
+            println!(
+                "{:<12} {:>8} {:>14} {:>12} {:>10}",
+                "стратегия", "стъпки", "макс. размер", "субституции", "завършила"
+            );
+
+            for &strategy in &Strategy::ALL {
+                let mut statistics = Statistics::default();
+                let result = term.normalize_with(strategy, fuel, false, &mut statistics);
+
+                println!(
+                    "{:<12} {:>8} {:>14} {:>12} {:>10}",
+                    strategy.to_string(),
+                    statistics.steps,
+                    statistics.max_size,
+                    statistics.substitution_work,
+                    if result.is_some() { "да" } else { "не" }
+                );
+            }
+        },
+        Command::Random {
+            size,
+            measure,
+            count,
+            seed,
+        } => {
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            let mut counter = enumeration::Counter::new(measure);
+
+            for _ in 0..count {
+                match counter.sample(size, 0, &mut rng) {
+                    Some(term) => println!("{}", NamedTerm::from_unnamed(&term)),
+                    None => {
+                        println!("няма затворени термове с размер {}", size);
+                        break;
+                    },
+                }
+            }
+        },
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,81 @@
+use super::Term;
+use std::collections::HashSet;
+
+impl Term {
+    /// Извършва η-контракция, ако термът е η-редекс `λ x. M x`, където `x` не
+    /// е свободна в `M`.
+    ///
+    /// Ако термът не е η-редекс, връща `None`.
+    pub fn eta_contract(&self) -> Option<Term> {
+        match self {
+            Term::Lambda(x, t) => match &**t {
+                Term::Apply(t1, t2) if **t2 == Term::Var(x.clone()) => {
+                    if t1.free_vars().contains(x) {
+                        None
+                    } else {
+                        Some((**t1).clone())
+                    }
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Извършва една стъпка на η-редукция върху най-левия най-външен
+    /// η-редекс.
+    ///
+    /// Връща `None`, ако в терма няма η-редекси.
+    pub fn eta_reduce_step(&self) -> Option<Term> {
+        match self {
+            Term::Var(_) => None,
+            Term::Apply(t1, t2) => t1
+                .eta_reduce_step()
+                .map(|t| Term::apply(t, (**t2).clone()))
+                .or_else(|| t2.eta_reduce_step().map(|t| Term::apply((**t1).clone(), t))),
+            Term::Lambda(x, t) => self
+                .eta_contract()
+                .or_else(|| t.eta_reduce_step().map(|t| Term::lambda(x.clone(), t))),
+        }
+    }
+
+    /// Връща η-нормалната форма на терма.
+    pub fn eta_normalize(&self) -> Term {
+        match self {
+            Term::Var(x) => Term::var(x.clone()),
+            Term::Apply(t1, t2) => Term::apply(t1.eta_normalize(), t2.eta_normalize()),
+            Term::Lambda(x, t) => {
+                let term = Term::lambda(x.clone(), t.eta_normalize());
+                term.eta_contract().unwrap_or(term)
+            },
+        }
+    }
+
+    /// Извършва η-разширение `times` пъти, т.е. превръща `M` в
+    /// `λ x1. ... λ xn. M x1 ... xn`, където `n = times`.
+    ///
+    /// Имената на новите променливи се избират така, че да не се срещат
+    /// свободно в `M`.
+    pub fn eta_expand(&self, times: usize) -> Term {
+        let mut used: HashSet<String> = self.free_vars();
+        let mut names = Vec::new();
+
+        for _ in 0..times {
+            let name = Self::fresh_name(&used);
+            used.insert(name.clone());
+            names.push(name);
+        }
+
+        let term = names
+            .iter()
+            .fold(self.clone(), |t, name| Term::apply(t, Term::var(name.clone())));
+
+        names
+            .into_iter()
+            .rev()
+            .fold(term, |t, name| Term::lambda(name, t))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,46 @@
+use crate::term;
+
+#[test]
+fn eta_contract() {
+    assert_eq!(term!(λ x. f x).eta_contract(), Some(term!(f)));
+    assert_eq!(term!(λ x. f y x).eta_contract(), Some(term!(f y)));
+    assert_eq!(term!(λ x. (λ y. y) x).eta_contract(), Some(term!(λ y. y)));
+
+    assert_eq!(term!(λ x. x).eta_contract(), None);
+    assert_eq!(term!(λ x. x x).eta_contract(), None);
+    assert_eq!(term!(λ x. f x y).eta_contract(), None);
+    assert_eq!(term!(f x).eta_contract(), None);
+}
+
+#[test]
+fn eta_reduce_step() {
+    assert_eq!(term!(x y).eta_reduce_step(), None);
+    assert_eq!(
+        term!(λ x. λ y. f x y).eta_reduce_step(),
+        Some(term!(λ x. f x))
+    );
+    assert_eq!(
+        term!(λ x. x (λ y. f y)).eta_reduce_step(),
+        Some(term!(λ x. x f))
+    );
+}
+
+#[test]
+fn eta_normalize() {
+    assert_eq!(term!(λ x. x).eta_normalize(), term!(λ x. x));
+    assert_eq!(term!(λ x. λ y. f x y).eta_normalize(), term!(f));
+    assert_eq!(term!(λ x. (λ y. f y) x).eta_normalize(), term!(f));
+    assert_eq!(term!(λ x. λ y. y x).eta_normalize(), term!(λ x. λ y. y x));
+}
+
+#[test]
+fn eta_expand() {
+    assert_eq!(term!(f).eta_expand(0), term!(f));
+    assert_eq!(term!(f).eta_expand(1), term!(λ x. f x));
+    assert_eq!(term!(f).eta_expand(2), term!(λ x. λ y. f x y));
+    assert_eq!(term!(x y).eta_expand(2), term!(λ z. λ w. x y z w));
+    assert_eq!(term!(λ x. x).eta_expand(1), term!(λ x. (λ x. x) x));
+
+    let term = term!(f (λ x. x) z);
+    assert_eq!(term.eta_expand(3).eta_normalize(), term);
+}
@@ -0,0 +1,164 @@
+use super::Term;
+use crate::unnamed::Term as UnnamedTerm;
+use std::fmt::{self, Display};
+
+/// Крайно приближение на дърво на Бьом.
+///
+/// Разрешимият терм с глава нормална форма `λ x1. ... λ xn. y M1 ... Mk`
+/// има за дърво възел с абстракциите `x1, ..., xn`, главата `y` и
+/// поддървета - дърветата на `M1, ..., Mk`. На неразрешимите термове
+/// съответства `⊥`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BohmTree {
+    /// Неразрешим подтерм или отрязано поддърво.
+    Bottom,
+    Node {
+        binders: Vec<String>,
+        head: String,
+        children: Vec<BohmTree>,
+    },
+}
+
+impl Term {
+    /// Извършва една стъпка на главна редукция. Виж
+    /// `unnamed::Term::head_reduce_step`.
+    pub fn head_reduce_step(&self) -> Option<Term> {
+        match self {
+            Term::Var(_) => None,
+            Term::Lambda(x, t) => t.head_reduce_step().map(|t| Term::lambda(x.clone(), t)),
+            Term::Apply(t1, t2) => match &**t1 {
+                Term::Lambda(x, body) => Some(body.substitute(x, t2)),
+                _ => t1.head_reduce_step().map(|t| Term::apply(t, (**t2).clone())),
+            },
+        }
+    }
+
+    /// Редуцира терма с главна редукция до глава нормална форма, но не
+    /// повече от `fuel` стъпки.
+    ///
+    /// Връща `None`, ако стъпките не са стигнали.
+    pub fn head_normal_form(&self, fuel: usize) -> Option<Term> {
+        let mut term = self.clone();
+
+        for _ in 0..fuel {
+            match term.head_reduce_step() {
+                Some(next) => term = next,
+                None => return Some(term),
+            }
+        }
+
+        term.head_reduce_step().map_or(Some(term), |_| None)
+    }
+
+    /// Проверява дали термът е разрешим. Виж `unnamed::Term::is_solvable`.
+    pub fn is_solvable(&self, fuel: usize) -> Option<bool> {
+        UnnamedTerm::from_named(self).0.is_solvable(fuel)
+    }
+
+    /// Връща приближението на дървото на Бьом на терма до дълбочина
+    /// `depth`.
+    ///
+    /// За всеки подтерм се правят най-много `fuel` стъпки на главна
+    /// редукция. Подтермовете, за които не е намерена глава нормална форма,
+    /// както и поддърветата под дълбочина `depth`, се заменят с `⊥`.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::term;
+    ///
+    /// let y = term!(λ f. (λ x. f (x x)) (λ x. f (x x)));
+    ///
+    /// assert_eq!(
+    ///     y.bohm_tree(3, 100).to_string(),
+    ///     "λ f. f\n\
+    ///      └── f\n    \
+    ///          └── f\n        \
+    ///              └── ⊥"
+    /// );
+    /// ```
+    pub fn bohm_tree(&self, depth: usize, fuel: usize) -> BohmTree {
+        if depth == 0 {
+            return BohmTree::Bottom;
+        }
+
+        let normal_form = match self.head_normal_form(fuel) {
+            Some(term) => term,
+            None => return BohmTree::Bottom,
+        };
+
+        let mut term = &normal_form;
+        let mut binders = Vec::new();
+        while let Term::Lambda(x, t) = term {
+            binders.push(x.clone());
+            term = t;
+        }
+
+        let mut args = Vec::new();
+        while let Term::Apply(t1, t2) = term {
+            args.push(&**t2);
+            term = t1;
+        }
+
+        let head = match term {
+            Term::Var(x) => x.clone(),
+            _ => unreachable!("head normal form without head variable"),
+        };
+
+        let children = args
+            .iter()
+            .rev()
+            .map(|arg| arg.bohm_tree(depth - 1, fuel))
+            .collect();
+
+        BohmTree::Node {
+            binders,
+            head,
+            children,
+        }
+    }
+}
+
+impl BohmTree {
+    fn fmt_indented(&self, f: &mut fmt::Formatter, prefix: &str) -> fmt::Result {
+        match self {
+            BohmTree::Bottom => write!(f, "⊥"),
+            BohmTree::Node {
+                binders,
+                head,
+                children,
+            } => {
+                for x in binders {
+                    write!(f, "λ {}. ", x)?;
+                }
+                write!(f, "{}", head)?;
+
+                for (i, child) in children.iter().enumerate() {
+                    let (branch, indent) = if i + 1 == children.len() {
+                        ("└── ", "    ")
+                    } else {
+                        ("├── ", "│   ")
+                    };
+
+                    write!(f, "\n{}{}", prefix, branch)?;
+                    child.fmt_indented(f, &format!("{}{}", prefix, indent))?;
+                }
+
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Формат за принтиране.
+///
+/// Всеки възел се принтира на отделен ред, а поддърветата му - под него с
+/// отстъп.
+impl Display for BohmTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_indented(f, "")
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+use crate::named::{BohmTree, Term};
+use crate::term;
+
+#[test]
+fn head_normal_form() {
+    assert_eq!(term!(x ((λ y. y) z)).head_normal_form(0), Some(term!(x ((λ y. y) z))));
+    assert_eq!(
+        term!((λ x. λ y. x y) y).head_normal_form(1),
+        Some(term!(λ z. y z))
+    );
+    assert_eq!(term!((λ x. x) y).head_normal_form(0), None);
+
+    let omega = term!((λ x. x x) (λ x. x x));
+    assert_eq!(omega.head_normal_form(100), None);
+    assert_eq!(omega.is_solvable(100), Some(false));
+    assert_eq!(term!(λ x. x ((λ x. x x) (λ x. x x))).is_solvable(0), Some(true));
+}
+
+#[test]
+fn bohm_tree() {
+    let omega = term!((λ x. x x) (λ x. x x));
+    assert_eq!(omega.bohm_tree(10, 100), BohmTree::Bottom);
+    assert_eq!(term!(x).bohm_tree(0, 100), BohmTree::Bottom);
+
+    let tree = term!(λ x. x ((λ y. y) z) ((λ x. x x) (λ x. x x))).bohm_tree(10, 100);
+    assert_eq!(
+        tree,
+        BohmTree::Node {
+            binders: vec!["x".to_string()],
+            head: "x".to_string(),
+            children: vec![
+                BohmTree::Node {
+                    binders: vec![],
+                    head: "z".to_string(),
+                    children: vec![],
+                },
+                BohmTree::Bottom,
+            ],
+        }
+    );
+    assert_eq!(tree.to_string(), "λ x. x\n├── z\n└── ⊥");
+}
+
+#[test]
+fn bohm_tree_of_fixed_point() {
+    // Y f =β f (Y f), затова дървото на Y е безкраен клон от f.
+    let y = term!(λ f. (λ x. f (x x)) (λ x. f (x x)));
+
+    let mut tree = y.bohm_tree(5, 100);
+    let mut depth = 0;
+
+    while let BohmTree::Node { head, children, .. } = tree {
+        assert_eq!(head, "f");
+        assert_eq!(children.len(), 1);
+
+        tree = children.into_iter().next().unwrap();
+        depth += 1;
+    }
+
+    assert_eq!(depth, 5);
+
+    let y = Term::apply(y, term!(λ x. λ y. y));
+    assert_eq!(y.bohm_tree(5, 100).to_string(), "λ y. y");
+}
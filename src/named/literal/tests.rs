@@ -1,5 +1,4 @@
 use crate::named::Term;
-use crate::term;
 
 #[test]
 fn macro_var() {
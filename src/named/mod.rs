@@ -1,4 +1,10 @@
+mod eta;
+mod head;
 mod literal;
+mod reduction;
 mod term;
+mod zipper;
 
+pub use self::head::BohmTree;
 pub use self::term::Term;
+pub use self::zipper::Zipper;
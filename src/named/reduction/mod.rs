@@ -0,0 +1,21 @@
+use super::Term;
+use crate::unnamed::{Convertibility, Term as UnnamedTerm};
+
+impl Term {
+    /// Проверява дали два терма са β-конвертируеми (или βη-конвертируеми,
+    /// ако `eta` е `true`).
+    ///
+    /// Термовете се превръщат в безименни в общ контекст от имена на
+    /// свободните променливи, след което се сравняват нормалните им форми.
+    /// Виж `unnamed::Term::convertible`.
+    pub fn convertible(&self, other: &Term, fuel: usize, eta: bool) -> Convertibility {
+        let mut names = Vec::new();
+        let t1 = UnnamedTerm::from_named_with(self, &mut names);
+        let t2 = UnnamedTerm::from_named_with(other, &mut names);
+
+        t1.convertible(&t2, fuel, eta)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,60 @@
+use crate::term;
+use crate::unnamed::Convertibility;
+
+#[test]
+fn convertible() {
+    assert_eq!(
+        term!(λ x. x).convertible(&term!(λ y. y), 0, false),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        term!((λ x. x) y).convertible(&term!(y), 1, false),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        term!((λ x. λ y. x) z).convertible(&term!(λ w. z), 1, false),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        term!((λ x. λ y. x) y).convertible(&term!(λ w. y), 1, false),
+        Convertibility::Equal
+    );
+
+    assert_eq!(
+        term!(x).convertible(&term!(y), 10, false),
+        Convertibility::DifferentNormalForms
+    );
+    assert_eq!(
+        term!((λ x. λ y. x) y).convertible(&term!(λ y. y), 10, false),
+        Convertibility::DifferentNormalForms
+    );
+
+    assert_eq!(
+        term!((λ x. x x) (λ x. x x)).convertible(&term!(y), 10, false),
+        Convertibility::Undecided
+    );
+    assert_eq!(
+        term!((λ x. x x) (λ x. x x)).convertible(&term!((λ y. y y) (λ z. z z)), 10, false),
+        Convertibility::Equal
+    );
+}
+
+#[test]
+fn convertible_eta() {
+    assert_eq!(
+        term!(λ x. f x).convertible(&term!(f), 10, false),
+        Convertibility::DifferentNormalForms
+    );
+    assert_eq!(
+        term!(λ x. f x).convertible(&term!(f), 10, true),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        term!(λ x. λ y. (λ z. f z) x y).convertible(&term!(f), 10, true),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        term!(λ x. x x).convertible(&term!(x), 10, true),
+        Convertibility::DifferentNormalForms
+    );
+}
@@ -1,10 +1,16 @@
+use crate::lambda::{self, LambdaTerm, View, ViewMut};
 use crate::unnamed::Term as UnnamedTerm;
 use serde_derive::{Serialize, Deserialize};
 use std::collections::HashSet;
-use std::fmt::{self, Display};
+use std::fmt::{self, Debug, Display};
+use std::mem;
+use std::rc::Rc;
 
 /// Ламбда терм
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// Всички операции върху терма, включително `Clone`, `PartialEq`, `Debug` и
+/// `Drop`, използват явен стек, така че работят и за много дълбоки термове.
+/// Само сериализацията и десериализацията са рекурсивни.
 #[derive(Serialize, Deserialize)]
 pub enum Term {
     Var(String),
@@ -40,115 +46,413 @@ impl Term {
     ///
     /// Използва автоматично генериран контекст от имена.
     pub fn from_unnamed(unnamed: &UnnamedTerm) -> Self {
-        let free_vars = LexicographicalNames::new(&Self::FV_LETTERS);
-        let bound_vars = LexicographicalNames::new(&Self::ARG_LETTERS);
+        Self::from_unnamed_with(unnamed, &[])
+    }
+
+    /// Превръща безименен ламбда терм в именуван, като свободната
+    /// променлива с индекс `i` получава името `names[i]`.
+    ///
+    /// Обратна операция на `unnamed::Term::from_named`. Останалите свободни
+    /// и свързаните променливи получават автоматично генерирани имена,
+    /// различни от тези в `names`.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::named::Term;
+    /// use hw::{term, unnamed_term};
+    ///
+    /// let names = vec!["x".to_string(), "b".to_string()];
+    /// assert_eq!(
+    ///     Term::from_unnamed_with(&unnamed_term!(λ 0 1 2 3), &names),
+    ///     term!(λ y. y x b a)
+    /// );
+    /// ```
+    pub fn from_unnamed_with(unnamed: &UnnamedTerm, names: &[String]) -> Self {
+        let free_vars = LexicographicalNames::avoiding(&Self::FV_LETTERS, names);
+        let bound_vars = LexicographicalNames::avoiding(&Self::ARG_LETTERS, names);
 
-        Self::from_unnamed_inner(unnamed, 0, &free_vars, &bound_vars)
+        Self::from_unnamed_inner(unnamed, 0, names, &free_vars, &bound_vars)
     }
 
     fn from_unnamed_inner(
         unnamed: &UnnamedTerm,
         depth: usize,
+        names: &[String],
         free_vars: &LexicographicalNames,
         bound_vars: &LexicographicalNames,
     ) -> Self {
-        match unnamed {
-            &UnnamedTerm::Var(i) => {
-                if i < depth {
-                    Self::var(bound_vars.get(depth - i))
-                } else {
-                    Self::var(free_vars.get(i - depth + 1))
-                }
-            },
-            UnnamedTerm::Apply(t1, t2) => Self::apply(
-                Self::from_unnamed_inner(t1, depth, free_vars, bound_vars),
-                Self::from_unnamed_inner(t2, depth, free_vars, bound_vars),
-            ),
-            UnnamedTerm::Lambda(t) => Self::lambda(
-                bound_vars.get(depth + 1),
-                Self::from_unnamed_inner(t, depth + 1, free_vars, bound_vars),
-            ),
+        enum Frame<'a> {
+            Visit(&'a UnnamedTerm, usize),
+            Apply,
+            Lambda(String),
         }
+
+        let mut frames = vec![Frame::Visit(unnamed, depth)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(&UnnamedTerm::Var(i), depth) => {
+                    if i < depth {
+                        results.push(Self::var(bound_vars.get(depth - i)));
+                    } else if let Some(name) = names.get(i - depth) {
+                        results.push(Self::var(name.clone()));
+                    } else {
+                        results.push(Self::var(free_vars.get(i - depth - names.len() + 1)));
+                    }
+                },
+                Frame::Visit(UnnamedTerm::Apply(t1, t2), depth) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2, depth));
+                    frames.push(Frame::Visit(t1, depth));
+                },
+                Frame::Visit(UnnamedTerm::Lambda(t), depth) => {
+                    frames.push(Frame::Lambda(bound_vars.get(depth + 1)));
+                    frames.push(Frame::Visit(t, depth + 1));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(Self::apply(t1, t2));
+                },
+                Frame::Lambda(x) => {
+                    let t = results.pop().unwrap();
+                    results.push(Self::lambda(x, t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
     }
 
     /// Изпълнява субституцията `term[var -> subs]`
+    ///
+    /// Когато абстракция `λ x. t` би прихванала свободна променлива на `subs`,
+    /// `x` се преименува на първото автоматично генерирано име, което не е
+    /// свободно нито в `subs`, нито в `t`. Преименуването е субституция на
+    /// `x` в `t`, след която се замества `var`. Двете субституции се пазят в
+    /// явен стек от продължения, а не в стека на Rust.
     pub fn substitute(&self, var: &str, subs: &Term) -> Term {
-        let fv_subs = subs.free_vars();
+        /// Субституцията `[var -> subs]` заедно със свободните променливи
+        /// на `subs`.
+        struct Substitution {
+            var: String,
+            subs: Term,
+            fv_subs: HashSet<String>,
+        }
 
-        match self {
-            Term::Var(x) if x == var => subs.clone(),
-            Term::Var(x) => Term::var(x.clone()),
-            Term::Apply(t1, t2) => Term::apply(t1.substitute(var, subs), t2.substitute(var, subs)),
-            Term::Lambda(x, t) if x == var => Term::Lambda(x.clone(), t.clone()),
-            Term::Lambda(x, t) if fv_subs.get(x).is_some() => {
-                let fv_term = t.free_vars();
-
-                let name_generator = LexicographicalNames::new(&Self::ARG_LETTERS);
-                let name = (1..)
-                    .map(|i| name_generator.get(i))
-                    .skip_while(|name| fv_subs.get(name).is_some() || fv_term.get(name).is_some())
-                    .next()
-                    .unwrap();
-
-                let term = t.substitute(x, &Term::var(name.clone())).substitute(var, subs);
-                Term::lambda(name, term)
-            },
-            Term::Lambda(x, t) => Term::lambda(x.clone(), t.substitute(var, subs)),
+        impl Substitution {
+            fn new(var: String, subs: Term) -> Rc<Self> {
+                let fv_subs = subs.free_vars();
+                Rc::new(Substitution { var, subs, fv_subs })
+            }
+        }
+
+        /// Подтерм на началния терм или междинен резултат от преименуване.
+        enum Subterm<'a> {
+            Borrowed(&'a Term),
+            Owned(Term),
+        }
+
+        enum Shape<'a> {
+            Var(String),
+            Apply(Subterm<'a>, Subterm<'a>),
+            Lambda(String, Subterm<'a>),
+        }
+
+        impl<'a> Subterm<'a> {
+            fn get(&self) -> &Term {
+                match self {
+                    Subterm::Borrowed(term) => term,
+                    Subterm::Owned(term) => term,
+                }
+            }
+
+            fn into_owned(self) -> Term {
+                match self {
+                    Subterm::Borrowed(term) => term.clone(),
+                    Subterm::Owned(term) => term,
+                }
+            }
+
+            fn split(self) -> Shape<'a> {
+                let take = |t: &mut Box<Term>| {
+                    Subterm::Owned(mem::replace(&mut **t, Term::var(String::new())))
+                };
+
+                match self {
+                    Subterm::Borrowed(Term::Var(x)) => Shape::Var(x.clone()),
+                    Subterm::Borrowed(Term::Apply(t1, t2)) => {
+                        Shape::Apply(Subterm::Borrowed(t1), Subterm::Borrowed(t2))
+                    },
+                    Subterm::Borrowed(Term::Lambda(x, t)) => {
+                        Shape::Lambda(x.clone(), Subterm::Borrowed(t))
+                    },
+                    Subterm::Owned(mut term) => match &mut term {
+                        Term::Var(x) => Shape::Var(mem::take(x)),
+                        Term::Apply(t1, t2) => Shape::Apply(take(t1), take(t2)),
+                        Term::Lambda(x, t) => Shape::Lambda(mem::take(x), take(t)),
+                    },
+                }
+            }
+        }
+
+        enum Frame<'a> {
+            Visit(Subterm<'a>, Rc<Substitution>),
+            /// Прилага субституцията към последния резултат.
+            Then(Rc<Substitution>),
+            Apply,
+            Lambda(String),
+        }
+
+        let mut frames = vec![Frame::Visit(
+            Subterm::Borrowed(self),
+            Substitution::new(var.to_string(), subs.clone()),
+        )];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(term, substitution) => match term.split() {
+                    Shape::Var(x) if x == substitution.var => {
+                        results.push(substitution.subs.clone())
+                    },
+                    Shape::Var(x) => results.push(Term::var(x)),
+                    Shape::Apply(t1, t2) => {
+                        frames.push(Frame::Apply);
+                        frames.push(Frame::Visit(t2, substitution.clone()));
+                        frames.push(Frame::Visit(t1, substitution));
+                    },
+                    Shape::Lambda(x, t) if x == substitution.var => {
+                        results.push(Term::lambda(x, t.into_owned()))
+                    },
+                    Shape::Lambda(x, t) if substitution.fv_subs.contains(&x) => {
+                        let mut used = t.get().free_vars();
+                        used.extend(substitution.fv_subs.iter().cloned());
+                        let name = Self::fresh_name(&used);
+
+                        frames.push(Frame::Lambda(name.clone()));
+                        frames.push(Frame::Then(substitution));
+                        frames.push(Frame::Visit(t, Substitution::new(x, Term::var(name))));
+                    },
+                    Shape::Lambda(x, t) => {
+                        frames.push(Frame::Lambda(x));
+                        frames.push(Frame::Visit(t, substitution));
+                    },
+                },
+                Frame::Then(substitution) => {
+                    let term = results.pop().unwrap();
+                    frames.push(Frame::Visit(Subterm::Owned(term), substitution));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(Term::apply(t1, t2));
+                },
+                Frame::Lambda(x) => {
+                    let t = results.pop().unwrap();
+                    results.push(Term::lambda(x, t));
+                },
+            }
         }
+
+        results.pop().unwrap()
+    }
+
+    /// Връща първото автоматично генерирано име на променлива, което не
+    /// присъства в `used`.
+    pub(crate) fn fresh_name(used: &HashSet<String>) -> String {
+        let name_generator = LexicographicalNames::new(&Self::ARG_LETTERS);
+
+        (1..)
+            .map(|i| name_generator.get(i))
+            .find(|name| !used.contains(name))
+            .unwrap()
     }
 
     /// Връща свободните променливи на терма
-    fn free_vars(&self) -> HashSet<String> {
+    pub(crate) fn free_vars(&self) -> HashSet<String> {
         let mut fv = HashSet::new();
         self.fill_free_vars(&mut vec![], &mut fv);
         fv
     }
 
     fn fill_free_vars<'a>(&'a self, args: &mut Vec<&'a str>, fv: &mut HashSet<String>) {
+        enum Frame<'a> {
+            Visit(&'a Term),
+            Unbind,
+        }
+
+        let mut frames = vec![Frame::Visit(self)];
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(Term::Var(x)) => {
+                    if args.iter().find(|arg| **arg == *x).is_none() {
+                        fv.insert(x.clone());
+                    }
+                },
+                Frame::Visit(Term::Apply(t1, t2)) => {
+                    frames.push(Frame::Visit(t2));
+                    frames.push(Frame::Visit(t1));
+                },
+                Frame::Visit(Term::Lambda(x, t)) => {
+                    args.push(x);
+                    frames.push(Frame::Unbind);
+                    frames.push(Frame::Visit(t));
+                },
+                Frame::Unbind => {
+                    args.pop();
+                },
+            }
+        }
+    }
+
+    /// Премества децата на терма, които не са променливи, в `stack`, като
+    /// ги заменя с променливи.
+    fn take_children(&mut self, stack: &mut Vec<Term>) {
+        let mut take = |t: &mut Box<Term>| {
+            if let Term::Var(_) = **t {
+                return;
+            }
+
+            stack.push(mem::replace(&mut **t, Term::Var(String::new())));
+        };
+
         match self {
-            Term::Var(x) => {
-                if args.iter().find(|arg| **arg == *x).is_none() {
-                    fv.insert(x.clone());
-                }
-            },
+            Term::Var(_) => {},
             Term::Apply(t1, t2) => {
-                t1.fill_free_vars(args, fv);
-                t2.fill_free_vars(args, fv);
-            },
-            Term::Lambda(x, t) => {
-                args.push(x);
-                t.fill_free_vars(args, fv);
-                args.pop();
+                take(t1);
+                take(t2);
             },
+            Term::Lambda(_, t) => take(t),
         }
     }
 }
 
-/// Формат за принтиране.
-///
-/// Използва се от `println!("{}", ...)`
-impl Display for Term {
+impl Clone for Term {
+    fn clone(&self) -> Self {
+        enum Frame<'a> {
+            Visit(&'a Term),
+            Apply,
+            Lambda(&'a str),
+        }
+
+        let mut frames = vec![Frame::Visit(self)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(Term::Var(x)) => results.push(Term::var(x.clone())),
+                Frame::Visit(Term::Apply(t1, t2)) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2));
+                    frames.push(Frame::Visit(t1));
+                },
+                Frame::Visit(Term::Lambda(x, t)) => {
+                    frames.push(Frame::Lambda(x));
+                    frames.push(Frame::Visit(t));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(Term::apply(t1, t2));
+                },
+                Frame::Lambda(x) => {
+                    let t = results.pop().unwrap();
+                    results.push(Term::lambda(x, t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+}
+
+impl PartialEq for Term {
+    fn eq(&self, other: &Self) -> bool {
+        let mut stack = vec![(self, other)];
+
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (Term::Var(x), Term::Var(y)) if x == y => {},
+                (Term::Apply(s1, s2), Term::Apply(t1, t2)) => {
+                    stack.push((s2, t2));
+                    stack.push((s1, t1));
+                },
+                (Term::Lambda(x, s), Term::Lambda(y, t)) if x == y => stack.push((s, t)),
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl Eq for Term {}
+
+impl Debug for Term {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use Term::*;
+        lambda::fmt_debug(self, f, |x, f| write!(f, "{:?}, ", x))
+    }
+}
 
-        match self {
-            Var(x) => write!(f, "{}", x),
-            Apply(t1, t2) => {
-                match **t1 {
-                    Var(_) | Apply(_, _) => write!(f, "{}", t1)?,
-                    _ => write!(f, "({})", t1)?,
-                }
+impl Drop for Term {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        self.take_children(&mut stack);
 
-                write!(f, " ")?;
+        while let Some(mut term) = stack.pop() {
+            term.take_children(&mut stack);
+        }
+    }
+}
 
-                match **t2 {
-                    Var(_) => write!(f, "{}", t2),
-                    _ => write!(f, "({})", t2),
-                }
-            },
-            Lambda(x, t) => write!(f, "λ {}. {}", x, t),
+impl LambdaTerm for Term {
+    type Var = String;
+    type Binder = String;
+
+    fn var(var: String) -> Self {
+        Term::Var(var)
+    }
+
+    fn apply(t1: Term, t2: Term) -> Self {
+        Term::apply(t1, t2)
+    }
+
+    fn lambda(binder: String, body: Term) -> Self {
+        Term::Lambda(binder, Box::new(body))
+    }
+
+    fn view(&self) -> View<'_, Self> {
+        match self {
+            Term::Var(x) => View::Var(x),
+            Term::Apply(t1, t2) => View::Apply(t1, t2),
+            Term::Lambda(x, t) => View::Lambda(x, t),
         }
     }
+
+    fn view_mut(&mut self) -> ViewMut<'_, Self> {
+        match self {
+            Term::Var(x) => ViewMut::Var(x),
+            Term::Apply(t1, t2) => ViewMut::Apply(t1, t2),
+            Term::Lambda(x, t) => ViewMut::Lambda(x, t),
+        }
+    }
+
+    fn fmt_binder(binder: &String, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "λ {}. ", binder)
+    }
+}
+
+/// Формат за принтиране.
+///
+/// Използва се от `println!("{}", ...)`
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lambda::fmt(self, f)
+    }
 }
 
 /// Генерира имена на променливи.
@@ -168,15 +472,46 @@ impl Display for Term {
 /// ```
 struct LexicographicalNames<'a> {
     base: &'a [char],
+    /// Номерата на пропуснатите имена, във възходящ ред.
+    skip: Vec<usize>,
 }
 
 impl<'a> LexicographicalNames<'a> {
     pub fn new(base: &'a [char]) -> Self {
-        LexicographicalNames { base }
+        LexicographicalNames {
+            base,
+            skip: Vec::new(),
+        }
+    }
+
+    /// Като `new`, но пропуска имената от `avoid`, така че никое генерирано
+    /// име не съвпада с тях.
+    pub fn avoiding(base: &'a [char], avoid: &[String]) -> Self {
+        let mut skip = avoid
+            .iter()
+            .filter_map(|name| {
+                name.chars().try_fold(0, |index, c| {
+                    let digit = base.iter().position(|&b| b == c)?;
+                    Some(index * base.len() + digit + 1)
+                })
+            })
+            .filter(|&index| index > 0)
+            .collect::<Vec<_>>();
+
+        skip.sort_unstable();
+        skip.dedup();
+
+        LexicographicalNames { base, skip }
     }
 
     pub fn get(&self, index: usize) -> String {
         let mut index = index;
+        for &skipped in &self.skip {
+            if skipped <= index {
+                index += 1;
+            }
+        }
+
         let mut result = Vec::new();
 
         while index > 0 {
@@ -102,3 +102,71 @@ fn substitute_with_lambda() {
         term!(λ z. (λ z. x y z) (λ y. z y))
     );
 }
+
+#[test]
+fn substitute_renames_nested_binders() {
+    assert_eq!(
+        term!(λ y. λ z. x y z).substitute("x", &term!(y z)),
+        term!(λ w. λ u. (y z) w u)
+    );
+    assert_eq!(
+        term!(λ y. λ y. x y).substitute("x", &term!(y)),
+        term!(λ z. λ z. y z)
+    );
+}
+
+/// Имената на преименуваните абстракции са същите като при рекурсивната
+/// субституция - първото име, което не е свободно нито в `subs`, нито в
+/// тялото на абстракцията.
+#[test]
+fn substitute_fresh_names() {
+    let cases = vec![
+        (term!(λ y. x (λ z. z)), "x", term!(y), "λ z. y (λ x. x)"),
+        (term!((λ y. x) (λ x. y)), "x", term!(λ w. y), "(λ z. λ w. y) (λ x. y)"),
+        (term!(λ x. λ y. z (λ z. x y z)), "z", term!(x y), "λ w. λ u. x y (λ z. w u z)"),
+    ];
+
+    for (term, var, subs, expected) in cases {
+        assert_eq!(term.substitute(var, &subs).to_string(), expected, "{}", term);
+    }
+
+    let lambdas = (0..1000).fold(term!(x y), |t, _| Term::lambda("y", t));
+    let substituted = lambdas.substitute("x", &term!(y));
+    assert_eq!(UnnamedTerm::from_named(&substituted).1, vec!["y"]);
+}
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", term!(x)), "Var(\"x\")");
+    assert_eq!(
+        format!("{:?}", term!(λ x. x (λ y. z))),
+        "Lambda(\"x\", Apply(Var(\"x\"), Lambda(\"y\", Var(\"z\"))))"
+    );
+}
+
+/// Достатъчно дълбоко, за да препълни стека при рекурсивно обхождане.
+const DEPTH: usize = 100_000;
+
+#[test]
+fn deep_terms() {
+    let spine = (1..DEPTH).fold(term!(x), |t, _| Term::apply(t, term!(y)));
+    let lambdas = (0..DEPTH).fold(term!(x y), |t, _| Term::lambda("z", t));
+    let nested = (0..DEPTH).fold(term!(x), |t, _| Term::apply(term!(y), t));
+
+    for term in &[spine, lambdas, nested] {
+        let copy = term.clone();
+        assert_eq!(copy, *term);
+
+        let unnamed = UnnamedTerm::from_named(term).0;
+        assert_eq!(UnnamedTerm::from_named(&Term::from_unnamed(&unnamed)).0, unnamed);
+
+        let substituted = term.substitute("x", &term!(λ z. y z));
+        assert_ne!(substituted, *term);
+        assert!(substituted.free_vars().contains("y"));
+        assert!(term.substitute("x", &term!(y)).free_vars().contains("y"));
+
+        let text = term.to_string();
+        assert!(text.len() > DEPTH);
+        assert!(format!("{:?}", term).len() > DEPTH);
+    }
+}
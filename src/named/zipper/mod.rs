@@ -0,0 +1,56 @@
+use super::Term;
+use crate::path::{self, Path};
+use std::mem;
+
+impl Term {
+    /// Връща подтерма, до който води `path`, или `None`, ако пътят не
+    /// съществува в терма.
+    pub fn get(&self, path: &Path) -> Option<&Term> {
+        path::get(self, path)
+    }
+
+    /// Като `get`, но връща изменима референция.
+    pub fn get_mut(&mut self, path: &Path) -> Option<&mut Term> {
+        path::get_mut(self, path)
+    }
+
+    /// Заменя подтерма, до който води `path`, с `new` и връща стария.
+    ///
+    /// Свободните променливи на `new` могат да бъдат прихванати от
+    /// абстракциите над подтерма. Връща `None` и не променя терма, ако пътят
+    /// не съществува.
+    pub fn replace(&mut self, path: &Path, new: Term) -> Option<Term> {
+        self.get_mut(path).map(|term| mem::replace(term, new))
+    }
+}
+
+/// Именуван терм с фокус върху един от подтермовете му. Виж `path::Zipper`.
+///
+/// # Пример
+///
+/// ```
+/// use hw::named::{Term, Zipper};
+/// use hw::path::Step;
+/// use hw::term;
+///
+/// let mut zipper = Zipper::new(term!(λ x. x (λ y. z)));
+/// assert!(zipper.down(Step::Body));
+/// assert!(zipper.down(Step::Right));
+/// assert!(zipper.down(Step::Body));
+/// assert_eq!(zipper.focus(), &term!(z));
+/// assert_eq!(zipper.scope(), vec!["x", "y"]);
+///
+/// zipper.replace(Term::var("x"));
+/// assert_eq!(zipper.into_term(), term!(λ x. x (λ y. x)));
+/// ```
+pub type Zipper = path::Zipper<Term>;
+
+impl Zipper {
+    /// Проверява дали името `x` е свързано от абстракция над фокуса.
+    pub fn is_bound(&self, x: &str) -> bool {
+        self.scope().iter().any(|name| name == x)
+    }
+}
+
+#[cfg(test)]
+mod tests;
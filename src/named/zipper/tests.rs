@@ -0,0 +1,61 @@
+use super::Zipper;
+use crate::named::Term;
+use crate::path::{Path, Step};
+use crate::term;
+
+#[test]
+fn get_and_replace() {
+    let mut term = term!((λ x. x y) (λ x. λ y. z));
+
+    let path = Path::from(vec![Step::Right, Step::Body, Step::Body]);
+    assert_eq!(term.get(&path), Some(&term!(z)));
+    assert_eq!(term.get(&path.child(Step::Body)), None);
+
+    assert_eq!(term.replace(&path, term!(x y)), Some(term!(z)));
+    assert_eq!(term, term!((λ x. x y) (λ x. λ y. x y)));
+
+    assert_eq!(term.replace(&Path::from(vec![Step::Body]), term!(x)), None);
+    assert_eq!(term, term!((λ x. x y) (λ x. λ y. x y)));
+}
+
+#[test]
+fn navigation() {
+    let term = term!((λ x. x y) (λ x. λ x. z));
+    let mut zipper = Zipper::new(term.clone());
+
+    assert!(zipper.scope().is_empty());
+    assert!(!zipper.down(Step::Body));
+
+    assert!(zipper.down(Step::Right));
+    assert!(zipper.down(Step::Body));
+    assert!(zipper.down(Step::Body));
+    assert_eq!(zipper.focus(), &term!(z));
+    assert_eq!(zipper.scope(), vec!["x", "x"]);
+    assert_eq!(zipper.depth(), 2);
+    assert!(zipper.is_bound("x"));
+    assert!(!zipper.is_bound("z"));
+    assert_eq!(zipper.path().to_string(), "RBB");
+
+    assert_eq!(zipper.up(), Some(Step::Body));
+    assert_eq!(zipper.focus(), &term!(λ x. z));
+
+    zipper.top();
+    assert!(zipper.follow(&Path::from(vec![Step::Left, Step::Body, Step::Right])));
+    assert_eq!(zipper.focus(), &term!(y));
+    assert_eq!(zipper.scope(), vec!["x"]);
+    assert!(!zipper.follow(&Path::from(vec![Step::Body])));
+
+    assert_eq!(zipper.into_term(), term);
+}
+
+#[test]
+fn editing() {
+    let mut zipper = Zipper::new(term!(λ f. f (g x)));
+
+    zipper.follow(&Path::from(vec![Step::Body, Step::Right, Step::Left]));
+    assert_eq!(zipper.replace(Term::var("f")), term!(g));
+
+    zipper.up();
+    *zipper.focus_mut() = Term::lambda("x", zipper.focus().clone());
+    assert_eq!(zipper.into_term(), term!(λ f. f (λ x. f x)));
+}
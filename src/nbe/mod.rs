@@ -0,0 +1,191 @@
+use crate::machine::Stack;
+use crate::unnamed::Term;
+use std::cell::{Cell, OnceCell};
+use std::rc::Rc;
+
+/// Нормализира `term` чрез нормализация чрез оценяване (normalization by
+/// evaluation).
+///
+/// Термът се оценява до семантична стойност, в която абстракциите са
+/// затваряния, а след това стойността се превръща обратно в терм. Аргументите
+/// се оценяват мързеливо и най-много веднъж, така че се намира нормалната
+/// форма на всеки терм, който има такава, но без да се копира целият терм
+/// при всяка β-стъпка.
+///
+/// `fuel` ограничава броя на прилаганията на затваряния (β-стъпки). Връща
+/// `None`, ако те не са стигнали.
+///
+/// # Пример
+///
+/// ```
+/// use hw::{nbe, unnamed_term};
+///
+/// let term = unnamed_term!((λ λ 1 (1 0)) (λ 0 0));
+/// assert_eq!(nbe::normalize(&term, 10), Some(unnamed_term!(λ 0 0 (0 0))));
+/// ```
+pub fn normalize(term: &Term, fuel: usize) -> Option<Term> {
+    let normalizer = Normalizer { fuel: Cell::new(fuel) };
+    let value = normalizer.eval(term, &Stack::new())?;
+
+    normalizer.read_back(&value, 0)
+}
+
+/// Среда - стойностите на свързаните променливи, като индекс `i` отговаря на
+/// `i`-тия елемент.
+type Env<'a> = Stack<Rc<Thunk<'a>>>;
+
+/// Семантична стойност на терм.
+#[derive(Clone)]
+enum Value<'a> {
+    /// Абстракция - тялото ѝ и средата, в която се оценява.
+    Closure(&'a Term, Env<'a>),
+    /// Терм, който не може да бъде оценен повече, защото главата му е
+    /// променлива.
+    Neutral(Rc<Neutral<'a>>),
+}
+
+enum Neutral<'a> {
+    /// Свързана променлива, въведена при обратното превръщане. Номерира се
+    /// с de Bruijn ниво - броя на абстракциите над нея.
+    Bound(usize),
+    /// Свободна променлива на нормализирания терм.
+    Free(usize),
+    Apply(Rc<Neutral<'a>>, Rc<Thunk<'a>>),
+}
+
+/// Отложено оценяване на терм в среда. Стойността се запомня след първото
+/// оценяване, а термът и средата се освобождават още при започването му,
+/// така че изчислените аргументи не държат веригата от среди, от която са
+/// получени.
+struct Thunk<'a> {
+    code: Cell<Option<(&'a Term, Env<'a>)>>,
+    value: OnceCell<Value<'a>>,
+}
+
+impl<'a> Thunk<'a> {
+    fn delay(term: &'a Term, env: Env<'a>) -> Rc<Self> {
+        Rc::new(Thunk {
+            code: Cell::new(Some((term, env))),
+            value: OnceCell::new(),
+        })
+    }
+
+    fn ready(value: Value<'a>) -> Rc<Self> {
+        Rc::new(Thunk {
+            code: Cell::new(None),
+            value: OnceCell::from(value),
+        })
+    }
+}
+
+struct Normalizer {
+    fuel: Cell<usize>,
+}
+
+/// Продължение на оценяването - какво да се направи със стойността, когато
+/// тя бъде намерена.
+enum Frame<'a> {
+    /// Стойността се прилага към аргумента.
+    Arg(Rc<Thunk<'a>>),
+    /// Стойността се запомня в отложеното оценяване.
+    Update(Rc<Thunk<'a>>),
+}
+
+impl Normalizer {
+    fn eval<'a>(&self, term: &'a Term, env: &Env<'a>) -> Option<Value<'a>> {
+        self.run(term, env.clone(), Vec::new())
+    }
+
+    fn force<'a>(&self, thunk: &Rc<Thunk<'a>>) -> Option<Value<'a>> {
+        if let Some(value) = thunk.value.get() {
+            return Some(value.clone());
+        }
+
+        let (term, env) = thunk.code.take().unwrap();
+        self.run(term, env, vec![Frame::Update(thunk.clone())])
+    }
+
+    /// Оценява `term` в `env` и продължава с `frames`. Продълженията са в
+    /// явен стек, така че β-стъпките и оценяването на отложените аргументи
+    /// не използват стека на Rust.
+    fn run<'a>(
+        &self,
+        mut term: &'a Term,
+        mut env: Env<'a>,
+        mut frames: Vec<Frame<'a>>,
+    ) -> Option<Value<'a>> {
+        loop {
+            let mut value = match term {
+                &Term::Var(i) => match env.get(i) {
+                    Some(thunk) => match thunk.value.get() {
+                        Some(value) => value.clone(),
+                        None => {
+                            let (body, body_env) = thunk.code.take().unwrap();
+                            frames.push(Frame::Update(thunk.clone()));
+                            term = body;
+                            env = body_env;
+                            continue;
+                        },
+                    },
+                    None => Value::Neutral(Rc::new(Neutral::Free(i - env.len()))),
+                },
+                Term::Apply(t1, t2) => {
+                    frames.push(Frame::Arg(Thunk::delay(t2, env.clone())));
+                    term = t1;
+                    continue;
+                },
+                Term::Lambda(t) => Value::Closure(t, env.clone()),
+            };
+
+            loop {
+                match frames.pop() {
+                    None => return Some(value),
+                    Some(Frame::Update(thunk)) => {
+                        value = thunk.value.get_or_init(|| value).clone();
+                    },
+                    Some(Frame::Arg(arg)) => match value {
+                        Value::Closure(body, closure_env) => {
+                            let fuel = self.fuel.get().checked_sub(1)?;
+                            self.fuel.set(fuel);
+
+                            term = body;
+                            env = closure_env.push(arg);
+                            break;
+                        },
+                        Value::Neutral(neutral) => {
+                            value = Value::Neutral(Rc::new(Neutral::Apply(neutral, arg)));
+                        },
+                    },
+                }
+            }
+        }
+    }
+
+    /// Превръща стойността в терм в нормална форма, като `depth` е броят на
+    /// абстракциите над нея.
+    fn read_back(&self, value: &Value, depth: usize) -> Option<Term> {
+        match value {
+            Value::Closure(body, env) => {
+                let var = Thunk::ready(Value::Neutral(Rc::new(Neutral::Bound(depth))));
+                let value = self.eval(body, &env.push(var))?;
+
+                Some(Term::lambda(self.read_back(&value, depth + 1)?))
+            },
+            Value::Neutral(neutral) => self.read_back_neutral(neutral, depth),
+        }
+    }
+
+    fn read_back_neutral(&self, neutral: &Neutral, depth: usize) -> Option<Term> {
+        match neutral {
+            Neutral::Bound(level) => Some(Term::var(depth - level - 1)),
+            Neutral::Free(i) => Some(Term::var(i + depth)),
+            Neutral::Apply(fun, arg) => Some(Term::apply(
+                self.read_back_neutral(fun, depth)?,
+                self.read_back(&self.force(arg)?, depth)?,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,48 @@
+use super::normalize;
+use crate::fixtures::{self, church, factorial};
+use crate::unnamed::{Strategy, Term};
+use crate::unnamed_term;
+
+#[test]
+fn normalize_simple() {
+    assert_eq!(normalize(&unnamed_term!(0), 0), Some(unnamed_term!(0)));
+    assert_eq!(normalize(&unnamed_term!(λ 0 1), 0), Some(unnamed_term!(λ 0 1)));
+    assert_eq!(normalize(&unnamed_term!((λ 0) 1), 1), Some(unnamed_term!(1)));
+    assert_eq!(normalize(&unnamed_term!((λ 0) 1), 0), None);
+    assert_eq!(normalize(&unnamed_term!(λ (λ 1) 2), 1), Some(unnamed_term!(λ 0)));
+    assert_eq!(normalize(&unnamed_term!(λ λ (λ 2) 0), 1), Some(unnamed_term!(λ λ 1)));
+
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+    assert_eq!(normalize(&omega, 1000), None);
+    assert_eq!(normalize(&omega, 1_000_000), None);
+    assert_eq!(
+        normalize(&Term::apply(unnamed_term!(λ λ 0), omega), 1000),
+        Some(unnamed_term!(λ 0))
+    );
+}
+
+#[test]
+fn agrees_with_normal_order() {
+    for term in fixtures::sample_terms() {
+        assert_eq!(
+            normalize(&term, 1000),
+            term.normalize(Strategy::NormalOrder, 1000, false),
+            "{}",
+            term
+        );
+    }
+}
+
+#[test]
+fn church_numerals() {
+    let plus = unnamed_term!(λ λ λ λ 3 1 (2 1 0));
+    let term = Term::apply(Term::apply(plus, church(2)), church(3));
+    assert_eq!(normalize(&term, 100), Some(church(5)));
+
+    assert_eq!(normalize(&factorial(3), 1000), Some(church(6)));
+    assert_eq!(
+        normalize(&factorial(3), 1000),
+        factorial(3).normalize(Strategy::NormalOrder, 10000, false)
+    );
+    assert_eq!(normalize(&factorial(6), 100000), Some(church(720)));
+}
@@ -0,0 +1,530 @@
+use crate::unnamed::Term;
+use std::mem;
+use std::rc::Rc;
+
+/// Брой на взаимодействията при оптималната редукция.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Interactions {
+    /// Взаимодействия между абстракция и апликация, т.е. β-стъпки.
+    pub beta: usize,
+    /// Всички взаимодействия, включително β-стъпките и тези на управляващите
+    /// възли (разклонители, скоби и полумесеци).
+    pub total: usize,
+}
+
+/// Нормализира `term` с алгоритъма на Лампинг за оптимална редукция
+/// (експериментално).
+///
+/// Термът се превръща в мрежа от взаимодействия (interaction net) с възли за
+/// абстракция, апликация, разклонители (fan), скоби (bracket), полумесеци
+/// (croissant) и изтриване (eraser). Разклонителите споделят подтермове, а
+/// скобите и полумесеците следят нивата на вложеност, така че споделените
+/// редекси се свиват само веднъж - броят на β-стъпките е оптимален по Леви.
+///
+/// Мрежата се редуцира мързеливо - свива се първата активна двойка, до която
+/// стига прочитането на терма от корена. Нормалната форма се прочита от
+/// мрежата чрез контекстна семантика (Gonthier, Abadi, Lévy).
+///
+/// Връща нормалната форма и броя на взаимодействията или `None`, ако
+/// взаимодействията надхвърлят `fuel`.
+///
+/// # Panics
+///
+/// Ако мрежата стигне до състояние, което не представя терм. Това е грешка
+/// в превръщането или в правилата за взаимодействие, а не свойство на
+/// терма.
+///
+/// # Пример
+///
+/// ```
+/// use hw::{optimal, unnamed_term};
+///
+/// let term = unnamed_term!((λ 0 0) (λ 0));
+/// let (normal_form, interactions) = optimal::normalize(&term, 100).unwrap();
+///
+/// assert_eq!(normal_form, unnamed_term!(λ 0));
+/// assert_eq!(interactions.beta, 2);
+/// ```
+pub fn normalize(term: &Term, fuel: usize) -> Option<(Term, Interactions)> {
+    // Свободните променливи се затварят с допълнителни абстракции, които се
+    // премахват след прочитането на резултата.
+    let free = free_count(term, 0);
+    let closed = (0..free).fold(term.clone(), |t, _| Term::lambda(t));
+
+    let mut net = Net::from_term(&closed);
+
+    loop {
+        match net.read_back() {
+            Ok(mut result) => {
+                for _ in 0..free {
+                    result = match &mut result {
+                        Term::Lambda(body) => mem::replace(&mut **body, Term::var(0)),
+                        _ => unreachable!("free variables are not closed by abstractions"),
+                    };
+                }
+
+                return Some((result, net.interactions));
+            },
+            Err(Interrupt::Reduced) if net.interactions.total > fuel => return None,
+            Err(Interrupt::Reduced) => {},
+            Err(Interrupt::Stuck) => panic!("interaction net does not represent a term"),
+        }
+    }
+}
+
+/// Връща броя на свободните променливи, т.е. най-големия индекс на свободна
+/// променлива плюс едно.
+fn free_count(term: &Term, depth: usize) -> usize {
+    match term {
+        &Term::Var(i) if i < depth => 0,
+        &Term::Var(i) => i - depth + 1,
+        Term::Apply(t1, t2) => free_count(t1, depth).max(free_count(t2, depth)),
+        Term::Lambda(t) => free_count(t, depth + 1),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Kind {
+    /// Коренът на мрежата. Единственият му порт сочи терма.
+    Root,
+    /// Портове: главен (към контекста), тяло, свързана променлива.
+    Lambda,
+    /// Портове: главен (към функцията), контекст, аргумент.
+    Apply,
+    /// Портове: главен, ляв и десен допълнителен.
+    Fan,
+    /// Портове: главен, допълнителен. Отваря ниво при преминаване от
+    /// допълнителния към главния порт.
+    Croissant,
+    /// Портове: главен, допълнителен. Слива две нива при преминаване от
+    /// допълнителния към главния порт.
+    Bracket,
+    /// Единствен главен порт.
+    Eraser,
+}
+
+impl Kind {
+    fn arity(self) -> usize {
+        match self {
+            Kind::Root | Kind::Eraser => 1,
+            Kind::Croissant | Kind::Bracket => 2,
+            Kind::Lambda | Kind::Apply | Kind::Fan => 3,
+        }
+    }
+
+    fn is_control(self) -> bool {
+        matches!(self, Kind::Fan | Kind::Croissant | Kind::Bracket)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Port {
+    node: usize,
+    slot: usize,
+}
+
+fn port(node: usize, slot: usize) -> Port {
+    Port { node, slot }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    kind: Kind,
+    index: usize,
+    ports: [Port; 3],
+}
+
+/// Мрежа от взаимодействия. Възлите се пазят във вектор и се сочат по
+/// номер, а освободените номера се използват повторно.
+struct Net {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    interactions: Interactions,
+}
+
+/// Причина за прекъсване на прочитането на мрежата.
+enum Interrupt {
+    /// Прочитането е стигнало до активна двойка и я е редуцирало.
+    Reduced,
+    /// Мрежата не представя терм.
+    Stuck,
+}
+
+impl Net {
+    const ROOT: usize = 0;
+
+    fn from_term(term: &Term) -> Self {
+        let mut net = Net {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            interactions: Interactions::default(),
+        };
+
+        let root = net.alloc(Kind::Root, 0);
+        let top = net.encode(term, 0, &mut vec![]);
+        net.link(port(root, 0), top);
+
+        net
+    }
+
+    fn alloc(&mut self, kind: Kind, index: usize) -> usize {
+        let node = Node {
+            kind,
+            index,
+            ports: [port(0, 0); 3],
+        };
+
+        match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            },
+        }
+    }
+
+    fn peer(&self, p: Port) -> Port {
+        self.nodes[p.node].ports[p.slot]
+    }
+
+    fn link(&mut self, a: Port, b: Port) {
+        self.nodes[a.node].ports[a.slot] = b;
+        self.nodes[b.node].ports[b.slot] = a;
+    }
+
+    /// Построява мрежата на `term` на ниво `level` и връща порта, с който тя
+    /// се свързва към контекста си.
+    ///
+    /// За всяка абстракция над терма `binders` съдържа свободните краища на
+    /// пътищата от срещанията на свързаната ѝ променлива.
+    fn encode(&mut self, term: &Term, level: usize, binders: &mut Vec<Vec<Port>>) -> Port {
+        match term {
+            &Term::Var(i) => {
+                let croissant = self.alloc(Kind::Croissant, level);
+                let depth = binders.len();
+                binders[depth - i - 1].push(port(croissant, 0));
+
+                port(croissant, 1)
+            },
+            Term::Apply(t1, t2) => {
+                let app = self.alloc(Kind::Apply, level);
+
+                let fun = self.encode(t1, level, binders);
+                self.link(port(app, 0), fun);
+
+                // Свободните променливи на аргумента минават през скоби.
+                let counts = binders.iter().map(Vec::len).collect::<Vec<_>>();
+                let arg = self.encode(t2, level + 1, binders);
+                self.link(port(app, 2), arg);
+
+                for (occurrences, count) in binders.iter_mut().zip(counts) {
+                    for occurrence in &mut occurrences[count..] {
+                        let bracket = self.alloc(Kind::Bracket, level);
+                        self.link(port(bracket, 1), *occurrence);
+                        *occurrence = port(bracket, 0);
+                    }
+                }
+
+                port(app, 1)
+            },
+            Term::Lambda(t) => {
+                let lambda = self.alloc(Kind::Lambda, level);
+
+                binders.push(Vec::new());
+                let body = self.encode(t, level, binders);
+                let occurrences = binders.pop().unwrap();
+
+                self.link(port(lambda, 1), body);
+                let var = self.share(&occurrences, level);
+                self.link(port(lambda, 2), var);
+
+                port(lambda, 0)
+            },
+        }
+    }
+
+    /// Свързва срещанията на променлива в дърво от разклонители на ниво
+    /// `level` и връща порта към абстракцията.
+    fn share(&mut self, occurrences: &[Port], level: usize) -> Port {
+        match occurrences {
+            [] => port(self.alloc(Kind::Eraser, level), 0),
+            [occurrence] => *occurrence,
+            _ => {
+                let fan = self.alloc(Kind::Fan, level);
+                let (left, right) = occurrences.split_at(occurrences.len() / 2);
+
+                let left = self.share(left, level);
+                self.link(port(fan, 1), left);
+                let right = self.share(right, level);
+                self.link(port(fan, 2), right);
+
+                port(fan, 0)
+            },
+        }
+    }
+
+    /// Редуцира активната двойка от възлите `a` и `b`, чиито главни портове
+    /// са свързани.
+    fn rewrite(&mut self, a: usize, b: usize) -> Result<(), Interrupt> {
+        let (ka, kb) = (self.nodes[a].kind, self.nodes[b].kind);
+        let (ia, ib) = (self.nodes[a].index, self.nodes[b].index);
+        self.interactions.total += 1;
+
+        match (ka, kb) {
+            (Kind::Lambda, Kind::Apply) => self.beta(a, b),
+            (Kind::Apply, Kind::Lambda) => self.beta(b, a),
+            (Kind::Eraser, _) => self.erase(a, b),
+            (_, Kind::Eraser) => self.erase(b, a),
+            _ if ka == kb && ia == ib => self.annihilate(a, b),
+            _ if ka.is_control() && (!kb.is_control() || ia < ib) => return self.commute(a, b),
+            _ => return self.commute(b, a),
+        }
+
+        Ok(())
+    }
+
+    fn beta(&mut self, lambda: usize, app: usize) {
+        self.interactions.beta += 1;
+
+        let context = self.peer(port(app, 1));
+        let body = self.peer(port(lambda, 1));
+        self.link(context, body);
+
+        let arg = self.peer(port(app, 2));
+        let var = self.peer(port(lambda, 2));
+        self.link(arg, var);
+
+        self.free.extend(&[lambda, app]);
+    }
+
+    fn annihilate(&mut self, a: usize, b: usize) {
+        for slot in 1..self.nodes[a].kind.arity() {
+            let pa = self.peer(port(a, slot));
+            let pb = self.peer(port(b, slot));
+            self.link(pa, pb);
+        }
+
+        self.free.extend(&[a, b]);
+    }
+
+    fn erase(&mut self, eraser: usize, node: usize) {
+        for slot in 1..self.nodes[node].kind.arity() {
+            let copy = self.alloc(Kind::Eraser, 0);
+            let p = self.peer(port(node, slot));
+            self.link(port(copy, 0), p);
+        }
+
+        self.free.extend(&[eraser, node]);
+    }
+
+    /// Прекарва управляващия възел `control` през възела `node`, като
+    /// копира `node` (два пъти за разклонител) и слага копия на `control` на
+    /// допълнителните му портове. Полумесецът намалява нивото на `node`, а
+    /// скобата го увеличава.
+    ///
+    /// Полумесец не може да намали ниво 0 - тогава мрежата не представя терм.
+    fn commute(&mut self, control: usize, node: usize) -> Result<(), Interrupt> {
+        let Node {
+            kind: control_kind,
+            index: control_index,
+            ..
+        } = self.nodes[control];
+        let Node { kind, index, .. } = self.nodes[node];
+
+        let new_index = match control_kind {
+            Kind::Croissant => index.checked_sub(1).ok_or(Interrupt::Stuck)?,
+            Kind::Bracket => index + 1,
+            _ => index,
+        };
+
+        let copies = (1..control_kind.arity())
+            .map(|_| self.alloc(kind, new_index))
+            .collect::<Vec<_>>();
+        let controls = (1..kind.arity())
+            .map(|_| self.alloc(control_kind, control_index))
+            .collect::<Vec<_>>();
+
+        for (i, &c) in controls.iter().enumerate() {
+            for (j, &copy) in copies.iter().enumerate() {
+                self.link(port(c, j + 1), port(copy, i + 1));
+            }
+        }
+
+        for (i, &c) in controls.iter().enumerate() {
+            let p = self.peer(port(node, i + 1));
+            self.link(port(c, 0), p);
+        }
+
+        for (j, &copy) in copies.iter().enumerate() {
+            let p = self.peer(port(control, j + 1));
+            self.link(port(copy, 0), p);
+        }
+
+        self.free.extend(&[control, node]);
+        Ok(())
+    }
+
+    /// Прочита терма, представен от мрежата, започвайки от корена.
+    ///
+    /// Ако по пътя бъде срещната активна двойка, тя се редуцира и
+    /// прочитането се прекъсва с `Interrupt::Reduced`.
+    fn read_back(&mut self) -> Result<Term, Interrupt> {
+        self.read(port(Self::ROOT, 0), Context::default(), &mut vec![])
+    }
+
+    /// Прочита подтерма, до който води портът `from`, в контекст `context`.
+    ///
+    /// `scope` съдържа абстракциите над подтерма заедно с контекстите, в
+    /// които са достигнати.
+    fn read(
+        &mut self,
+        from: Port,
+        context: Context,
+        scope: &mut Vec<(usize, Context)>,
+    ) -> Result<Term, Interrupt> {
+        let mut from = from;
+        let mut context = context;
+
+        loop {
+            let to = self.peer(from);
+            let Node { kind, index, .. } = self.nodes[to.node];
+
+            if from.slot == 0 && to.slot == 0 && from.node != Self::ROOT {
+                self.rewrite(from.node, to.node)?;
+                return Err(Interrupt::Reduced);
+            }
+
+            match (kind, to.slot) {
+                (Kind::Lambda, 0) => {
+                    scope.push((to.node, context.clone()));
+                    let body = self.read(port(to.node, 1), context, scope);
+                    scope.pop();
+
+                    return Ok(Term::lambda(body?));
+                },
+                (Kind::Lambda, 2) => {
+                    return scope
+                        .iter()
+                        .rev()
+                        .position(|(node, c)| *node == to.node && c.agrees(&context, index))
+                        .map(Term::var)
+                        .ok_or(Interrupt::Stuck);
+                },
+                (Kind::Apply, 1) => {
+                    let fun = self.read(port(to.node, 0), context.clone(), scope)?;
+                    let arg = self.read(port(to.node, 2), context, scope)?;
+
+                    return Ok(Term::apply(fun, arg));
+                },
+                (Kind::Fan, 0) => {
+                    from = port(to.node, context.pop(index).ok_or(Interrupt::Stuck)?);
+                },
+                (Kind::Fan, side) => {
+                    context.push(index, side);
+                    from = port(to.node, 0);
+                },
+                (Kind::Croissant, 0) => {
+                    context.remove(index);
+                    from = port(to.node, 1);
+                },
+                (Kind::Croissant, _) => {
+                    context.insert(index);
+                    from = port(to.node, 0);
+                },
+                (Kind::Bracket, 0) => {
+                    context.split(index);
+                    from = port(to.node, 1);
+                },
+                (Kind::Bracket, _) => {
+                    context.merge(index);
+                    from = port(to.node, 0);
+                },
+                _ => return Err(Interrupt::Stuck),
+            }
+        }
+    }
+}
+
+/// Ниво на контекст - записва през кои портове на разклонителите на това
+/// ниво е минал пътят.
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Level {
+    Empty,
+    Port(usize, Rc<Level>),
+    Pair(Rc<Level>, Rc<Level>),
+}
+
+/// Контекст - безкрайна редица от нива, като липсващите нива са празни.
+#[derive(Debug, Clone, Default)]
+struct Context {
+    levels: Vec<Level>,
+}
+
+impl Context {
+    fn level(&mut self, i: usize) -> &mut Level {
+        if self.levels.len() <= i {
+            self.levels.resize(i + 1, Level::Empty);
+        }
+
+        &mut self.levels[i]
+    }
+
+    fn push(&mut self, i: usize, side: usize) {
+        let level = self.level(i);
+        *level = Level::Port(side, Rc::new(level.clone()));
+    }
+
+    fn pop(&mut self, i: usize) -> Option<usize> {
+        let level = self.level(i);
+
+        match level.clone() {
+            Level::Port(side, rest) => {
+                *level = (*rest).clone();
+                Some(side)
+            },
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.level(i);
+        self.levels.insert(i, Level::Empty);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.level(i);
+        self.levels.remove(i);
+    }
+
+    fn merge(&mut self, i: usize) {
+        self.level(i + 1);
+        let second = self.levels.remove(i + 1);
+        let first = self.level(i);
+
+        if *first != Level::Empty || second != Level::Empty {
+            *first = Level::Pair(Rc::new(first.clone()), Rc::new(second));
+        }
+    }
+
+    fn split(&mut self, i: usize) {
+        let (first, second) = match self.level(i).clone() {
+            Level::Pair(first, second) => ((*first).clone(), (*second).clone()),
+            _ => (Level::Empty, Level::Empty),
+        };
+
+        self.levels[i] = first;
+        self.levels.insert(i + 1, second);
+    }
+
+    /// Проверява дали двата контекста съвпадат на нивата под `n`.
+    fn agrees(&self, other: &Context, n: usize) -> bool {
+        let get = |c: &Context, i: usize| c.levels.get(i).cloned().unwrap_or(Level::Empty);
+        (0..n).all(|i| get(self, i) == get(other, i))
+    }
+}
+
+#[cfg(test)]
+mod tests;
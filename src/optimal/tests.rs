@@ -0,0 +1,113 @@
+use super::{normalize, port, Interactions, Interrupt, Kind, Net};
+use crate::fixtures::{self, church};
+use crate::unnamed::{Strategy, Term};
+use crate::unnamed_term;
+
+/// Брой стъпки на нормалната стратегия до нормалната форма.
+fn normal_order_steps(term: &Term) -> usize {
+    let mut term = term.clone();
+    let mut steps = 0;
+
+    while let Some(next) = term.reduce_step(Strategy::NormalOrder, false) {
+        term = next;
+        steps += 1;
+    }
+
+    steps
+}
+
+#[test]
+fn read_back_without_reduction() {
+    let terms = vec![
+        unnamed_term!(0),
+        unnamed_term!(λ 0),
+        unnamed_term!(λ λ 1 0),
+        unnamed_term!(λ λ 0 (λ 0 2 1)),
+        unnamed_term!(λ 0 0 0),
+        unnamed_term!(0 1 (λ 0 2)),
+    ];
+
+    for term in terms {
+        assert_eq!(normalize(&term, 0), Some((term.clone(), Interactions::default())));
+    }
+}
+
+#[test]
+fn normalize_simple() {
+    let (normal_form, interactions) = normalize(&unnamed_term!((λ 0) 1), 1).unwrap();
+    assert_eq!(normal_form, unnamed_term!(1));
+    assert_eq!(interactions.beta, 1);
+    assert_eq!(normalize(&unnamed_term!((λ 0) 1), 0), None);
+
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+    assert_eq!(normalize(&omega, 1000), None);
+
+    let (normal_form, interactions) =
+        normalize(&Term::apply(unnamed_term!(λ λ 0), omega), 1000).unwrap();
+    assert_eq!(normal_form, unnamed_term!(λ 0));
+    assert_eq!(interactions.beta, 1);
+}
+
+#[test]
+fn agrees_with_normal_order() {
+    for term in fixtures::sample_terms() {
+        assert_eq!(
+            normalize(&term, 10000).map(|(t, _)| t),
+            term.normalize(Strategy::NormalOrder, 1000, false),
+            "{}",
+            term
+        );
+    }
+}
+
+#[test]
+fn church_exponentiation() {
+    let cases = vec![(2, 2, 5), (2, 3, 6), (3, 2, 8), (3, 3, 10), (4, 2, 11)];
+
+    for (exponent, base, beta) in cases {
+        let term = Term::apply(church(exponent), church(base));
+        let (normal_form, interactions) = normalize(&term, 10000).unwrap();
+
+        assert_eq!(normal_form, church(base.pow(exponent as u32)));
+        assert_eq!(interactions.beta, beta);
+        assert!(interactions.beta < normal_order_steps(&term));
+    }
+
+    // 2 2 2 = 2^(2^2)
+    let term = Term::apply(Term::apply(church(2), church(2)), church(2));
+    let (normal_form, interactions) = normalize(&term, 10000).unwrap();
+    assert_eq!(normal_form, church(16));
+    assert_eq!(interactions.beta, 12);
+    assert_eq!(normal_order_steps(&term), 42);
+}
+
+#[test]
+fn self_application_tower() {
+    // (λ x. x x) ((λ x. x x) (... (λ x. x))) - нормалната стратегия прави
+    // експоненциално много стъпки, а оптималната - линейно много.
+    let delta = unnamed_term!(λ 0 0);
+    let mut term = unnamed_term!(λ 0);
+
+    for n in 1..=10 {
+        term = Term::apply(delta.clone(), term);
+        let (normal_form, interactions) = normalize(&term, 10000).unwrap();
+
+        assert_eq!(normal_form, unnamed_term!(λ 0));
+        assert_eq!(interactions.beta, 2 * n);
+
+        if n <= 5 {
+            assert_eq!(normal_order_steps(&term), (1 << (n + 1)) - 2);
+        }
+    }
+}
+
+#[test]
+fn croissant_at_level_zero_is_stuck() {
+    let mut net = Net::from_term(&unnamed_term!(λ 0));
+    let lambda = net.peer(port(Net::ROOT, 0)).node;
+    assert_eq!(net.nodes[lambda].index, 0);
+
+    let croissant = net.alloc(Kind::Croissant, 0);
+    net.link(port(croissant, 0), port(lambda, 0));
+    assert!(matches!(net.rewrite(croissant, lambda), Err(Interrupt::Stuck)));
+}
@@ -0,0 +1,80 @@
+use crate::unnamed::Term;
+
+/// Дълбочина, до която подтермовете на апликациите се обработват
+/// паралелно. По-надолу задачите са твърде малки и се обработват
+/// последователно.
+const PARALLEL_DEPTH: usize = 12;
+
+/// Извършва пълно развитие на всички редекси в терма (стъпка на Грос-Кнут).
+///
+/// Всички редекси се свиват едновременно, като вътрешните редекси и
+/// остатъците им се свиват преди външните. Независимите подтермове на
+/// апликациите се обработват паралелно в текущия пул от нишки на `rayon`.
+///
+/// # Пример
+///
+/// ```
+/// use hw::{parallel, unnamed_term};
+///
+/// let term = unnamed_term!(((λ 0) 1) ((λ λ 1) 2));
+/// assert_eq!(parallel::develop(&term), unnamed_term!(1 (λ 3)));
+/// ```
+pub fn develop(term: &Term) -> Term {
+    develop_at(term, 0)
+}
+
+fn develop_at(term: &Term, depth: usize) -> Term {
+    match term {
+        Term::Var(_) => term.clone(),
+        Term::Lambda(t) => Term::lambda(develop_at(t, depth + 1)),
+        Term::Apply(t1, t2) => {
+            let (fun, arg) = if depth < PARALLEL_DEPTH {
+                rayon::join(|| develop_at(t1, depth + 1), || develop_at(t2, depth + 1))
+            } else {
+                (develop_at(t1, depth + 1), develop_at(t2, depth + 1))
+            };
+
+            match &**t1 {
+                Term::Lambda(_) => Term::apply(fun, arg).contract().unwrap(),
+                _ => Term::apply(fun, arg),
+            }
+        },
+    }
+}
+
+/// Нормализира `term` паралелно със стратегията на Грос-Кнут - на всеки
+/// рунд се извършва пълно развитие на всички редекси (вж. `develop`).
+///
+/// Стратегията е нормализираща, затова при достатъчно рундове дава
+/// същата нормална форма като нормалната стратегия. Изпълнява се в
+/// текущия пул от нишки на `rayon`, така че броят нишки се задава с
+/// `rayon::ThreadPool::install`.
+///
+/// Връща `None`, ако нормалната форма не е достигната за `fuel` рунда.
+pub fn normalize(term: &Term, fuel: usize) -> Option<Term> {
+    let mut term = term.clone();
+    let mut fuel = fuel;
+
+    while !is_normal(&term) {
+        if fuel == 0 {
+            return None;
+        }
+
+        term = develop(&term);
+        fuel -= 1;
+    }
+
+    Some(term)
+}
+
+fn is_normal(term: &Term) -> bool {
+    match term {
+        Term::Var(_) => true,
+        Term::Lambda(t) => is_normal(t),
+        Term::Apply(t1, _) if matches!(**t1, Term::Lambda(_)) => false,
+        Term::Apply(t1, t2) => is_normal(t1) && is_normal(t2),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,50 @@
+use super::{develop, normalize};
+use crate::fixtures::{self, church};
+use crate::unnamed::{Strategy, Term};
+use crate::unnamed_term;
+
+#[test]
+fn develop_all_redexes() {
+    assert_eq!(develop(&unnamed_term!(0 1)), unnamed_term!(0 1));
+    assert_eq!(develop(&unnamed_term!((λ 0) 1)), unnamed_term!(1));
+
+    // Остатъците на вътрешните редекси също се свиват.
+    assert_eq!(develop(&unnamed_term!((λ 0 0) ((λ 0) 1))), unnamed_term!(1 1));
+    assert_eq!(develop(&unnamed_term!((λ 0) (λ 0) 1)), unnamed_term!((λ 0) 1));
+
+    // Новосъздадените редекси не се свиват.
+    assert_eq!(develop(&unnamed_term!((λ 0 1) (λ 0))), unnamed_term!((λ 0) 0));
+}
+
+#[test]
+fn agrees_with_normal_order() {
+    for term in fixtures::sample_terms() {
+        assert_eq!(
+            normalize(&term, 1000),
+            term.normalize(Strategy::NormalOrder, 1000, false),
+            "{}",
+            term
+        );
+    }
+}
+
+#[test]
+fn fuel() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+    assert_eq!(normalize(&omega, 100), None);
+
+    let term = unnamed_term!((λ 0) ((λ 0) 1));
+    assert_eq!(normalize(&term, 0), None);
+    assert_eq!(normalize(&term, 1), Some(unnamed_term!(1)));
+}
+
+#[test]
+fn thread_pools() {
+    let mult = unnamed_term!(λ λ λ 2 (1 0));
+    let term = Term::apply(Term::apply(mult, church(12)), church(15));
+
+    for jobs in 1..=4 {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build().unwrap();
+        assert_eq!(pool.install(|| normalize(&term, 100)), Some(church(180)));
+    }
+}
@@ -0,0 +1,135 @@
+//! Адресиране на подтермове.
+
+use crate::lambda::{LambdaTerm, View, ViewMut};
+use std::fmt::{self, Display};
+
+mod zipper;
+
+pub use self::zipper::Zipper;
+
+/// Стъпка от връх към негово дете.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Step {
+    /// Към функцията на апликация.
+    Left,
+    /// Към аргумента на апликация.
+    Right,
+    /// Към тялото на абстракция.
+    Body,
+}
+
+/// Път от корена на терм до негов подтерм.
+///
+/// Принтира се като последователност от `L`, `R` и `B`, а празният път -
+/// като `ε`.
+///
+/// # Пример
+///
+/// ```
+/// use hw::path::{Path, Step};
+/// use hw::unnamed_term;
+///
+/// let mut term = unnamed_term!(0 (λ 1 0));
+/// let path = Path::from(vec![Step::Right, Step::Body, Step::Left]);
+/// assert_eq!(path.to_string(), "RBL");
+///
+/// assert_eq!(term.get(&path), Some(&unnamed_term!(1)));
+/// assert_eq!(term.replace(&path, unnamed_term!(λ 0)), Some(unnamed_term!(1)));
+/// assert_eq!(term, unnamed_term!(0 (λ (λ 0) 0)));
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Path {
+    steps: Vec<Step>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn push(&mut self, step: Step) {
+        self.steps.push(step);
+    }
+
+    pub fn pop(&mut self) -> Option<Step> {
+        self.steps.pop()
+    }
+
+    /// Връща пътя, продължен със стъпката `step`.
+    pub fn child(&self, step: Step) -> Path {
+        let mut path = self.clone();
+        path.push(step);
+        path
+    }
+
+    /// Проверява дали пътят започва с `prefix`.
+    pub fn starts_with(&self, prefix: &Path) -> bool {
+        self.steps.starts_with(&prefix.steps)
+    }
+}
+
+impl From<Vec<Step>> for Path {
+    fn from(steps: Vec<Step>) -> Self {
+        Path { steps }
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.steps.is_empty() {
+            return write!(f, "ε");
+        }
+
+        for step in &self.steps {
+            match step {
+                Step::Left => write!(f, "L")?,
+                Step::Right => write!(f, "R")?,
+                Step::Body => write!(f, "B")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Връща подтерма на `term`, до който води `path`, или `None`, ако пътят не
+/// съществува в терма.
+pub fn get<'a, T: LambdaTerm>(term: &'a T, path: &Path) -> Option<&'a T> {
+    path.steps.iter().try_fold(term, |term, step| match (term.view(), step) {
+        (View::Apply(t1, _), Step::Left) => Some(t1),
+        (View::Apply(_, t2), Step::Right) => Some(t2),
+        (View::Lambda(_, t), Step::Body) => Some(t),
+        _ => None,
+    })
+}
+
+/// Като `get`, но връща изменима референция.
+pub fn get_mut<'a, T: LambdaTerm>(term: &'a mut T, path: &Path) -> Option<&'a mut T> {
+    path.steps.iter().try_fold(term, |term, step| match (term.view_mut(), step) {
+        (ViewMut::Apply(t1, _), Step::Left) => Some(t1),
+        (ViewMut::Apply(_, t2), Step::Right) => Some(t2),
+        (ViewMut::Lambda(_, t), Step::Body) => Some(t),
+        _ => None,
+    })
+}
+
+/// Връща броя на абстракциите по пътя `path`, т.е. над подтерма, до който
+/// води той.
+pub fn binders(path: &Path) -> usize {
+    path.steps.iter().filter(|step| **step == Step::Body).count()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use super::{binders, get, Path, Step};
+use crate::{term, unnamed_term};
+
+#[test]
+fn display() {
+    assert_eq!(Path::new().to_string(), "ε");
+    assert_eq!(Path::from(vec![Step::Left, Step::Body, Step::Right]).to_string(), "LBR");
+}
+
+#[test]
+fn child_and_prefix() {
+    let path = Path::new().child(Step::Body).child(Step::Left);
+    assert_eq!(path.steps(), &[Step::Body, Step::Left]);
+    assert!(path.starts_with(&Path::from(vec![Step::Body])));
+    assert!(!path.starts_with(&Path::from(vec![Step::Left])));
+    assert_eq!(binders(&path), 1);
+}
+
+#[test]
+fn get_generic() {
+    let named = term!((λ x. x y) z);
+    let unnamed = unnamed_term!((λ 0 1) 2);
+
+    let path = Path::from(vec![Step::Left, Step::Body, Step::Right]);
+    assert_eq!(get(&named, &path), Some(&term!(y)));
+    assert_eq!(get(&unnamed, &path), Some(&unnamed_term!(1)));
+
+    assert_eq!(get(&named, &Path::new()), Some(&named));
+    assert_eq!(get(&named, &Path::from(vec![Step::Body])), None);
+    assert_eq!(get(&unnamed, &Path::from(vec![Step::Right, Step::Left])), None);
+}
@@ -0,0 +1,173 @@
+use super::{Path, Step};
+use crate::lambda::ViewMut;
+use crate::visit::Scoped;
+use std::fmt::{self, Debug};
+use std::mem;
+
+/// Частта от терма над фокуса на `Zipper`.
+#[derive(Debug)]
+enum Frame<T: Scoped> {
+    /// Фокусът е функцията на апликация с този аргумент.
+    Left(T),
+    /// Фокусът е аргументът на апликация с тази функция.
+    Right(T),
+    /// Фокусът е тялото на абстракция с тази свързана променлива.
+    Body(T::Binder),
+}
+
+/// Терм с фокус върху един от подтермовете му.
+///
+/// Придвижването на фокуса с една стъпка и промяната му отнемат константно
+/// време. Работи с всяко представяне - `unnamed::Zipper` и `named::Zipper`
+/// са съответните му варианти.
+///
+/// # Пример
+///
+/// ```
+/// use hw::path::Step;
+/// use hw::unnamed::{Term, Zipper};
+/// use hw::unnamed_term;
+///
+/// let mut zipper = Zipper::new(unnamed_term!(λ 0 (λ 2)));
+/// assert!(zipper.down(Step::Body));
+/// assert!(zipper.down(Step::Right));
+/// assert!(zipper.down(Step::Body));
+/// assert_eq!(zipper.focus(), &unnamed_term!(2));
+/// assert_eq!(zipper.depth(), 2);
+///
+/// zipper.replace(Term::var(1));
+/// assert_eq!(zipper.into_term(), unnamed_term!(λ 0 (λ 1)));
+/// ```
+pub struct Zipper<T: Scoped> {
+    focus: T,
+    context: Vec<Frame<T>>,
+}
+
+impl<T> Zipper<T>
+where
+    T: Scoped,
+    T::Var: Default,
+    T::Binder: Clone,
+{
+    /// Създава zipper с фокус корена на `term`.
+    pub fn new(term: T) -> Self {
+        Zipper {
+            focus: term,
+            context: Vec::new(),
+        }
+    }
+
+    pub fn focus(&self) -> &T {
+        &self.focus
+    }
+
+    pub fn focus_mut(&mut self) -> &mut T {
+        &mut self.focus
+    }
+
+    /// Заменя фокуса с `term` и връща стария.
+    pub fn replace(&mut self, term: T) -> T {
+        mem::replace(&mut self.focus, term)
+    }
+
+    /// Връща броя на абстракциите над фокуса.
+    pub fn depth(&self) -> usize {
+        self.context.iter().filter(|frame| matches!(frame, Frame::Body(_))).count()
+    }
+
+    /// Връща обхвата на фокуса - за именуваните термове това са имената,
+    /// свързани от абстракциите над него, като най-вътрешното е последно.
+    pub fn scope(&self) -> T::Scope {
+        let mut scope = T::Scope::default();
+
+        for frame in &self.context {
+            if let Frame::Body(binder) = frame {
+                T::enter(&mut scope, binder);
+            }
+        }
+
+        scope
+    }
+
+    /// Връща пътя от корена до фокуса.
+    pub fn path(&self) -> Path {
+        let steps = self.context.iter().map(|frame| match frame {
+            Frame::Left(_) => Step::Left,
+            Frame::Right(_) => Step::Right,
+            Frame::Body(_) => Step::Body,
+        });
+
+        Path::from(steps.collect::<Vec<_>>())
+    }
+
+    /// Премества фокуса към детето по `step`. Връща `false` и не мести
+    /// фокуса, ако той няма такова дете.
+    pub fn down(&mut self, step: Step) -> bool {
+        let (focus, frame) = match (self.focus.view_mut(), step) {
+            (ViewMut::Apply(t1, t2), Step::Left) => (take(t1), Frame::Left(take(t2))),
+            (ViewMut::Apply(t1, t2), Step::Right) => (take(t2), Frame::Right(take(t1))),
+            (ViewMut::Lambda(binder, t), Step::Body) => (take(t), Frame::Body(binder.clone())),
+            _ => return false,
+        };
+
+        self.focus = focus;
+        self.context.push(frame);
+        true
+    }
+
+    /// Премества фокуса към родителя му. Връща стъпката, с която се стига
+    /// от родителя до стария фокус, или `None`, ако фокусът е коренът.
+    pub fn up(&mut self) -> Option<Step> {
+        let frame = self.context.pop()?;
+        let focus = take(&mut self.focus);
+
+        let (focus, step) = match frame {
+            Frame::Left(t2) => (T::apply(focus, t2), Step::Left),
+            Frame::Right(t1) => (T::apply(t1, focus), Step::Right),
+            Frame::Body(binder) => (T::lambda(binder, focus), Step::Body),
+        };
+
+        self.focus = focus;
+        Some(step)
+    }
+
+    /// Премества фокуса по пътя `path`, започвайки от текущия фокус. Ако
+    /// някоя стъпка е невъзможна, фокусът остава там, докъдето е стигнал, и
+    /// се връща `false`.
+    pub fn follow(&mut self, path: &Path) -> bool {
+        path.steps().iter().all(|step| self.down(*step))
+    }
+
+    /// Премества фокуса към корена.
+    pub fn top(&mut self) {
+        while self.up().is_some() {}
+    }
+
+    /// Връща целия терм.
+    pub fn into_term(mut self) -> T {
+        self.top();
+        take(&mut self.focus)
+    }
+}
+
+impl<T> Debug for Zipper<T>
+where
+    T: Scoped + Debug,
+    T::Binder: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Zipper")
+            .field("focus", &self.focus)
+            .field("context", &self.context)
+            .finish()
+    }
+}
+
+/// Взима терма, като оставя на мястото му променлива.
+fn take<T>(term: &mut T) -> T
+where
+    T: Scoped,
+    T::Var: Default,
+{
+    mem::replace(term, T::var(T::Var::default()))
+}
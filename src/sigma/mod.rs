@@ -0,0 +1,364 @@
+use crate::unnamed::Term as UnnamedTerm;
+use std::fmt::{self, Display};
+
+/// Терм от λσ-смятането с явни субституции.
+///
+/// Разширява `unnamed::Term` със затваряния `M[s]` - терм, върху който
+/// предстои да се приложи субституцията `s`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Term {
+    Var(usize),
+    Apply(Box<Term>, Box<Term>),
+    Lambda(Box<Term>),
+    Closure(Box<Term>, Box<Subst>),
+}
+
+/// Явна субституция.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Subst {
+    /// Идентитет `id` - `n ↦ n`.
+    Id,
+    /// Отместване `↑` - `n ↦ n + 1`.
+    Shift,
+    /// `M · s` - `0 ↦ M`, `n + 1 ↦ n[s]`.
+    Cons(Box<Term>, Box<Subst>),
+    /// Композиция `s ∘ t` - първо се прилага `s`, после `t`.
+    Compose(Box<Subst>, Box<Subst>),
+}
+
+/// Правило за пренаписване на λσ-смятането.
+///
+/// Всички правила без `Beta` образуват σ-смятането, което изпълнява
+/// субституциите и винаги завършва.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Rule {
+    /// `(λ M) N → M[N · id]`
+    Beta,
+    /// `(M N)[s] → M[s] N[s]`
+    App,
+    /// `(λ M)[s] → λ M[0 · (s ∘ ↑)]`
+    Abs,
+    /// `M[s][t] → M[s ∘ t]`
+    Clos,
+    /// `n[id] → n`
+    VarId,
+    /// `n[↑] → n + 1`
+    VarShift,
+    /// `0[M · s] → M`
+    VarCons,
+    /// `(n + 1)[M · s] → n[s]`
+    VarSkip,
+    /// `n[↑ ∘ s] → (n + 1)[s]`
+    VarCompose,
+    /// `id ∘ s → s`
+    IdL,
+    /// `s ∘ id → s`
+    IdR,
+    /// `↑ ∘ (M · s) → s`
+    ShiftCons,
+    /// `(M · s) ∘ t → M[t] · (s ∘ t)`
+    Map,
+    /// `(s1 ∘ s2) ∘ t → s1 ∘ (s2 ∘ t)`
+    Ass,
+}
+
+impl Term {
+    pub fn var(n: usize) -> Self {
+        Term::Var(n)
+    }
+
+    pub fn apply(t1: Term, t2: Term) -> Self {
+        Term::Apply(Box::new(t1), Box::new(t2))
+    }
+
+    pub fn lambda(t: Term) -> Self {
+        Term::Lambda(Box::new(t))
+    }
+
+    pub fn closure(t: Term, s: Subst) -> Self {
+        Term::Closure(Box::new(t), Box::new(s))
+    }
+
+    /// Превръща безименен терм в терм без затваряния.
+    pub fn from_unnamed(term: &UnnamedTerm) -> Self {
+        match term {
+            UnnamedTerm::Var(n) => Term::var(*n),
+            UnnamedTerm::Apply(t1, t2) => {
+                Term::apply(Self::from_unnamed(t1), Self::from_unnamed(t2))
+            },
+            UnnamedTerm::Lambda(t) => Term::lambda(Self::from_unnamed(t)),
+        }
+    }
+
+    /// Превръща терма в безименен, ако в него няма затваряния.
+    pub fn to_unnamed(&self) -> Option<UnnamedTerm> {
+        match self {
+            Term::Var(n) => Some(UnnamedTerm::var(*n)),
+            Term::Apply(t1, t2) => Some(UnnamedTerm::apply(t1.to_unnamed()?, t2.to_unnamed()?)),
+            Term::Lambda(t) => Some(UnnamedTerm::lambda(t.to_unnamed()?)),
+            Term::Closure(_, _) => None,
+        }
+    }
+
+    /// Прилага правило на λσ-смятането върху самия терм (не върху
+    /// подтермовете му). Връща приложеното правило и резултата или `None`,
+    /// ако никое правило не е приложимо.
+    pub fn rewrite(&self) -> Option<(Rule, Term)> {
+        use Term::*;
+
+        match self {
+            Apply(t1, t2) => match &**t1 {
+                Lambda(body) => Some((
+                    Rule::Beta,
+                    Term::closure((**body).clone(), Subst::cons((**t2).clone(), Subst::Id)),
+                )),
+                _ => None,
+            },
+            Closure(t, s) => match (&**t, &**s) {
+                (Apply(t1, t2), _) => Some((
+                    Rule::App,
+                    Term::apply(
+                        Term::closure((**t1).clone(), (**s).clone()),
+                        Term::closure((**t2).clone(), (**s).clone()),
+                    ),
+                )),
+                (Lambda(t), _) => {
+                    let lifted =
+                        Subst::cons(Term::var(0), Subst::compose((**s).clone(), Subst::Shift));
+                    Some((Rule::Abs, Term::lambda(Term::closure((**t).clone(), lifted))))
+                },
+                (Closure(t, s1), _) => Some((
+                    Rule::Clos,
+                    Term::closure((**t).clone(), Subst::compose((**s1).clone(), (**s).clone())),
+                )),
+                (Var(n), Subst::Id) => Some((Rule::VarId, Term::var(*n))),
+                (Var(n), Subst::Shift) => Some((Rule::VarShift, Term::var(n + 1))),
+                (Var(0), Subst::Cons(t, _)) => Some((Rule::VarCons, (**t).clone())),
+                (Var(n), Subst::Cons(_, s)) => {
+                    Some((Rule::VarSkip, Term::closure(Term::var(n - 1), (**s).clone())))
+                },
+                (Var(n), Subst::Compose(s1, s2)) if **s1 == Subst::Shift => {
+                    Some((Rule::VarCompose, Term::closure(Term::var(n + 1), (**s2).clone())))
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Прилага правило върху най-левия най-външен подтерм или
+    /// подсубституция, за които има приложимо правило.
+    ///
+    /// Връща приложеното правило и резултата или `None`, ако термът е в
+    /// нормална форма.
+    pub fn step(&self) -> Option<(Rule, Term)> {
+        self.step_with(true)
+    }
+
+    /// Като `step`, но без правилото `Beta`, т.е. изпълнява само
+    /// субституциите.
+    pub fn sigma_step(&self) -> Option<(Rule, Term)> {
+        self.step_with(false)
+    }
+
+    fn step_with(&self, beta: bool) -> Option<(Rule, Term)> {
+        use Term::*;
+
+        if let Some((rule, term)) = self.rewrite() {
+            if beta || rule != Rule::Beta {
+                return Some((rule, term));
+            }
+        }
+
+        match self {
+            Var(_) => None,
+            Apply(t1, t2) => t1
+                .step_with(beta)
+                .map(|(r, t)| (r, Term::apply(t, (**t2).clone())))
+                .or_else(|| {
+                    t2.step_with(beta).map(|(r, t)| (r, Term::apply((**t1).clone(), t)))
+                }),
+            Lambda(t) => t.step_with(beta).map(|(r, t)| (r, Term::lambda(t))),
+            Closure(t, s) => t
+                .step_with(beta)
+                .map(|(r, t)| (r, Term::closure(t, (**s).clone())))
+                .or_else(|| {
+                    s.step_with(beta).map(|(r, s)| (r, Term::closure((**t).clone(), s)))
+                }),
+        }
+    }
+
+    /// Връща σ-нормалната форма на терма - изпълнява всички субституции.
+    ///
+    /// Ако в терма няма β-редекси под затваряния, резултатът няма
+    /// затваряния и е равен на резултата от мета-субституциите на
+    /// `unnamed::Term`.
+    pub fn sigma_normalize(&self) -> Term {
+        let mut term = self.clone();
+
+        while let Some((_, next)) = term.sigma_step() {
+            term = next;
+        }
+
+        term
+    }
+
+    /// Връща итератор по стъпките на λσ-редукцията на терма (вж. `step`).
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::sigma::{Rule, Term};
+    /// use hw::unnamed_term;
+    ///
+    /// let term = Term::from_unnamed(&unnamed_term!((λ 0) 1));
+    /// let rules = term.trace().map(|(rule, _)| rule).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(rules, vec![Rule::Beta, Rule::VarCons]);
+    /// ```
+    pub fn trace(&self) -> impl Iterator<Item = (Rule, Term)> {
+        let mut term = self.clone();
+
+        std::iter::from_fn(move || {
+            let (rule, next) = term.step()?;
+            term = next.clone();
+            Some((rule, next))
+        })
+    }
+}
+
+impl Subst {
+    pub fn cons(t: Term, s: Subst) -> Self {
+        Subst::Cons(Box::new(t), Box::new(s))
+    }
+
+    pub fn compose(s: Subst, t: Subst) -> Self {
+        Subst::Compose(Box::new(s), Box::new(t))
+    }
+
+    /// Прилага правило на σ-смятането върху самата субституция. Виж
+    /// `Term::rewrite`.
+    pub fn rewrite(&self) -> Option<(Rule, Subst)> {
+        use Subst::*;
+
+        match self {
+            Compose(s, t) => match (&**s, &**t) {
+                (Id, _) => Some((Rule::IdL, (**t).clone())),
+                (_, Id) => Some((Rule::IdR, (**s).clone())),
+                (Shift, Cons(_, t)) => Some((Rule::ShiftCons, (**t).clone())),
+                (Cons(m, s), _) => Some((
+                    Rule::Map,
+                    Subst::cons(
+                        Term::closure((**m).clone(), (**t).clone()),
+                        Subst::compose((**s).clone(), (**t).clone()),
+                    ),
+                )),
+                (Compose(s1, s2), _) => Some((
+                    Rule::Ass,
+                    Subst::compose((**s1).clone(), Subst::compose((**s2).clone(), (**t).clone())),
+                )),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn step_with(&self, beta: bool) -> Option<(Rule, Subst)> {
+        use Subst::*;
+
+        if let Some(result) = self.rewrite() {
+            return Some(result);
+        }
+
+        match self {
+            Id | Shift => None,
+            Cons(t, s) => t
+                .step_with(beta)
+                .map(|(r, t)| (r, Subst::cons(t, (**s).clone())))
+                .or_else(|| s.step_with(beta).map(|(r, s)| (r, Subst::cons((**t).clone(), s)))),
+            Compose(s, t) => s
+                .step_with(beta)
+                .map(|(r, s)| (r, Subst::compose(s, (**t).clone())))
+                .or_else(|| {
+                    t.step_with(beta).map(|(r, t)| (r, Subst::compose((**s).clone(), t)))
+                }),
+        }
+    }
+}
+
+/// Формат за принтиране.
+///
+/// Като при `unnamed::Term`, а затварянията се принтират като `M[s]`.
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Term::*;
+
+        match self {
+            Var(x) => write!(f, "{}", x),
+            Apply(t1, t2) => {
+                match **t1 {
+                    Lambda(_) => write!(f, "({})", t1)?,
+                    _ => write!(f, "{}", t1)?,
+                }
+
+                write!(f, " ")?;
+
+                match **t2 {
+                    Var(_) | Closure(_, _) => write!(f, "{}", t2),
+                    _ => write!(f, "({})", t2),
+                }
+            },
+            Lambda(t) => write!(f, "λ {}", t),
+            Closure(t, s) => match **t {
+                Var(_) | Closure(_, _) => write!(f, "{}[{}]", t, s),
+                _ => write!(f, "({})[{}]", t, s),
+            },
+        }
+    }
+}
+
+/// Формат за принтиране.
+///
+/// Композицията и `·` се принтират дясноасоциативно, като `·` не се смесва
+/// с `∘` без скоби, например `0 · (id ∘ ↑)`.
+impl Display for Subst {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Subst::*;
+
+        match self {
+            Id => write!(f, "id"),
+            Shift => write!(f, "↑"),
+            Cons(t, s) => {
+                match **t {
+                    Term::Var(_) | Term::Closure(_, _) => write!(f, "{} · ", t)?,
+                    _ => write!(f, "({}) · ", t)?,
+                }
+
+                match **s {
+                    Compose(_, _) => write!(f, "({})", s),
+                    _ => write!(f, "{}", s),
+                }
+            },
+            Compose(s, t) => {
+                match **s {
+                    Id | Shift => write!(f, "{} ∘ ", s)?,
+                    _ => write!(f, "({}) ∘ ", s)?,
+                }
+
+                match **t {
+                    Cons(_, _) => write!(f, "({})", t),
+                    _ => write!(f, "{}", t),
+                }
+            },
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests;
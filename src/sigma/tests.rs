@@ -0,0 +1,137 @@
+use super::{Rule, Subst, Term};
+use crate::unnamed::{Strategy, Term as UnnamedTerm};
+use crate::unnamed_term;
+
+fn sigma(term: UnnamedTerm) -> Term {
+    Term::from_unnamed(&term)
+}
+
+#[test]
+fn rewrite() {
+    let m = sigma(unnamed_term!(0 1));
+    let n = Term::var(3);
+
+    assert_eq!(
+        sigma(unnamed_term!((λ 0 1) 3)).rewrite(),
+        Some((Rule::Beta, Term::closure(m.clone(), Subst::cons(n.clone(), Subst::Id))))
+    );
+    assert_eq!(
+        Term::closure(m.clone(), Subst::Shift).rewrite(),
+        Some((
+            Rule::App,
+            Term::apply(
+                Term::closure(Term::var(0), Subst::Shift),
+                Term::closure(Term::var(1), Subst::Shift)
+            )
+        ))
+    );
+    assert_eq!(
+        Term::closure(Term::lambda(m.clone()), Subst::Id).rewrite(),
+        Some((
+            Rule::Abs,
+            Term::lambda(Term::closure(
+                m.clone(),
+                Subst::cons(Term::var(0), Subst::compose(Subst::Id, Subst::Shift))
+            ))
+        ))
+    );
+    assert_eq!(
+        Term::closure(Term::closure(m.clone(), Subst::Id), Subst::Shift).rewrite(),
+        Some((Rule::Clos, Term::closure(m, Subst::compose(Subst::Id, Subst::Shift))))
+    );
+
+    let var = |n: usize, s: Subst| Term::closure(Term::var(n), s).rewrite();
+    assert_eq!(var(2, Subst::Id), Some((Rule::VarId, Term::var(2))));
+    assert_eq!(var(2, Subst::Shift), Some((Rule::VarShift, Term::var(3))));
+    assert_eq!(var(0, Subst::cons(n.clone(), Subst::Id)), Some((Rule::VarCons, n.clone())));
+    assert_eq!(
+        var(2, Subst::cons(n.clone(), Subst::Shift)),
+        Some((Rule::VarSkip, Term::closure(Term::var(1), Subst::Shift)))
+    );
+    assert_eq!(
+        var(2, Subst::compose(Subst::Shift, Subst::Shift)),
+        Some((Rule::VarCompose, Term::closure(Term::var(3), Subst::Shift)))
+    );
+    assert_eq!(var(0, Subst::compose(Subst::Id, Subst::Shift)), None);
+}
+
+#[test]
+fn rewrite_subst() {
+    use Subst::*;
+
+    let cons = Subst::cons(Term::var(0), Shift);
+    assert_eq!(Subst::compose(Id, Shift).rewrite(), Some((Rule::IdL, Shift)));
+    assert_eq!(Subst::compose(Shift, Id).rewrite(), Some((Rule::IdR, Shift)));
+    assert_eq!(Subst::compose(Shift, cons.clone()).rewrite(), Some((Rule::ShiftCons, Shift)));
+    assert_eq!(
+        Subst::compose(cons, Shift).rewrite(),
+        Some((
+            Rule::Map,
+            Subst::cons(
+                Term::closure(Term::var(0), Shift),
+                Subst::compose(Shift, Shift)
+            )
+        ))
+    );
+    assert_eq!(
+        Subst::compose(Subst::compose(Shift, Shift), Shift).rewrite(),
+        Some((Rule::Ass, Subst::compose(Shift, Subst::compose(Shift, Shift))))
+    );
+    assert_eq!(Subst::compose(Shift, Shift).rewrite(), None);
+}
+
+#[test]
+fn sigma_agrees_with_substitution() {
+    let redexes = vec![
+        unnamed_term!((λ 0) 1),
+        unnamed_term!((λ 1) 2),
+        unnamed_term!((λ 0 2) 5),
+        unnamed_term!((λ λ 1) 0),
+        unnamed_term!((λ λ 1 0) (λ 0 1)),
+        unnamed_term!((λ λ λ 2 (λ 3 1 0)) (0 (λ 1))),
+    ];
+
+    for redex in redexes {
+        let (rule, term) = sigma(redex.clone()).rewrite().unwrap();
+
+        assert_eq!(rule, Rule::Beta);
+        assert_eq!(term.sigma_normalize().to_unnamed(), redex.contract(), "{}", redex);
+    }
+}
+
+#[test]
+fn trace() {
+    let terms = vec![
+        unnamed_term!((λ λ 1) 3 ((λ 0 0) (λ 0 0))),
+        unnamed_term!((λ λ 1 0 2) (λ 0) 5),
+        unnamed_term!((λ λ 1 (1 0)) (λ λ 1 (1 0)) (λ 3 0)),
+    ];
+
+    for term in terms {
+        let (_, last) = sigma(term.clone()).trace().take(1000).last().unwrap();
+
+        assert_eq!(last.step(), None);
+        assert_eq!(last.to_unnamed(), term.normalize(Strategy::NormalOrder, 100, false));
+    }
+
+    assert_eq!(sigma(unnamed_term!(0 (λ 0))).trace().next(), None);
+}
+
+#[test]
+fn display() {
+    let term = Term::closure(
+        Term::lambda(sigma(unnamed_term!(0 1))),
+        Subst::cons(
+            sigma(unnamed_term!(λ 0)),
+            Subst::compose(Subst::compose(Subst::Shift, Subst::Id), Subst::Shift),
+        ),
+    );
+    assert_eq!(term.to_string(), "(λ 0 1)[(λ 0) · ((↑ ∘ id) ∘ ↑)]");
+
+    let cons = Subst::cons(Term::var(1), Subst::Id);
+    let term = Term::apply(
+        Term::closure(Term::var(0), Subst::compose(Subst::Shift, cons)),
+        Term::closure(Term::closure(Term::var(2), Subst::Id), Subst::Shift),
+    );
+    assert_eq!(term.to_string(), "0[↑ ∘ (1 · id)] 2[id][↑]");
+}
@@ -0,0 +1,23 @@
+use super::{parse, with_large_stack};
+use hw::named::Term as NamedTerm;
+use hw::nbe;
+use hw::unnamed::Term as UnnamedTerm;
+
+/// Достатъчно дълбоко, за да препълни стека на нишка с размер по
+/// подразбиране.
+const DEPTH: usize = 100_000;
+
+#[test]
+fn parse_deep_term() {
+    let text = format!("{}Var(\"y\"){}", "Lambda(\"x\", ".repeat(DEPTH), ")".repeat(DEPTH));
+
+    let (term, normal_form) = with_large_stack(move || {
+        let term = UnnamedTerm::from_named(&parse::<NamedTerm>(&text)).0;
+        let normal_form = nbe::normalize(&term, 0);
+        (term, normal_form)
+    });
+
+    let expected = (0..DEPTH).fold(UnnamedTerm::var(DEPTH), |t, _| UnnamedTerm::lambda(t));
+    assert_eq!(term, expected);
+    assert_eq!(normal_form, Some(expected));
+}
@@ -0,0 +1,64 @@
+use super::Term;
+
+impl Term {
+    /// Извършва η-контракция, ако термът е η-редекс `λ (M 0)`, където `0` не е
+    /// свободна в `M`.
+    ///
+    /// Връща `M` с коригирани индекси на свободните променливи. Ако термът не
+    /// е η-редекс, връща `None`.
+    pub fn eta_contract(&self) -> Option<Term> {
+        match self {
+            Term::Lambda(t) => match &**t {
+                Term::Apply(t1, t2) if **t2 == Term::Var(0) && !t1.is_free(0) => {
+                    Some(t1.lower(0))
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Извършва една стъпка на η-редукция върху най-левия най-външен
+    /// η-редекс.
+    ///
+    /// Връща `None`, ако в терма няма η-редекси.
+    pub fn eta_reduce_step(&self) -> Option<Term> {
+        match self {
+            Term::Var(_) => None,
+            Term::Apply(t1, t2) => t1
+                .eta_reduce_step()
+                .map(|t| Term::apply(t, (**t2).clone()))
+                .or_else(|| t2.eta_reduce_step().map(|t| Term::apply((**t1).clone(), t))),
+            Term::Lambda(t) => self
+                .eta_contract()
+                .or_else(|| t.eta_reduce_step().map(Term::lambda)),
+        }
+    }
+
+    /// Връща η-нормалната форма на терма.
+    pub fn eta_normalize(&self) -> Term {
+        match self {
+            Term::Var(x) => Term::Var(*x),
+            Term::Apply(t1, t2) => Term::apply(t1.eta_normalize(), t2.eta_normalize()),
+            Term::Lambda(t) => {
+                let term = Term::lambda(t.eta_normalize());
+                term.eta_contract().unwrap_or(term)
+            },
+        }
+    }
+
+    /// Извършва η-разширение `times` пъти, т.е. превръща `M` в
+    /// `λ λ ... λ M 'n-1' ... '1' '0'`, където `n = times`.
+    pub fn eta_expand(&self, times: usize) -> Term {
+        let mut term = (0..times).fold(self.clone(), |t, _| t.raise(0));
+
+        for i in (0..times).rev() {
+            term = Term::apply(term, Term::var(i));
+        }
+
+        (0..times).fold(term, |t, _| Term::lambda(t))
+    }
+}
+
+#[cfg(test)]
+mod tests;
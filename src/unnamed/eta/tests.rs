@@ -0,0 +1,87 @@
+use crate::unnamed::Strategy;
+use crate::unnamed_term;
+
+#[test]
+fn eta_contract() {
+    assert_eq!(unnamed_term!(λ 1 0).eta_contract(), Some(unnamed_term!(0)));
+    assert_eq!(unnamed_term!(λ 3 2 0).eta_contract(), Some(unnamed_term!(2 1)));
+    assert_eq!(
+        unnamed_term!(λ (λ 0) 0).eta_contract(),
+        Some(unnamed_term!(λ 0))
+    );
+
+    assert_eq!(unnamed_term!(λ 0).eta_contract(), None);
+    assert_eq!(unnamed_term!(λ 0 0).eta_contract(), None);
+    assert_eq!(unnamed_term!(λ 0 1).eta_contract(), None);
+    assert_eq!(unnamed_term!(1 0).eta_contract(), None);
+}
+
+#[test]
+fn eta_reduce_step() {
+    assert_eq!(unnamed_term!(0 1).eta_reduce_step(), None);
+    assert_eq!(
+        unnamed_term!(λ λ 2 1 0).eta_reduce_step(),
+        Some(unnamed_term!(λ 1 0))
+    );
+    assert_eq!(
+        unnamed_term!(λ 0 (λ 3 0)).eta_reduce_step(),
+        Some(unnamed_term!(λ 0 2))
+    );
+    assert_eq!(
+        unnamed_term!((λ 1 0) (λ 1 0)).eta_reduce_step(),
+        Some(unnamed_term!(0 (λ 1 0)))
+    );
+}
+
+#[test]
+fn eta_normalize() {
+    assert_eq!(unnamed_term!(λ 0).eta_normalize(), unnamed_term!(λ 0));
+    assert_eq!(unnamed_term!(λ λ 2 1 0).eta_normalize(), unnamed_term!(0));
+    assert_eq!(
+        unnamed_term!(λ (λ 2 0) 0).eta_normalize(),
+        unnamed_term!(0)
+    );
+    assert_eq!(
+        unnamed_term!(λ λ 0 1).eta_normalize(),
+        unnamed_term!(λ λ 0 1)
+    );
+}
+
+#[test]
+fn eta_expand() {
+    assert_eq!(unnamed_term!(0).eta_expand(0), unnamed_term!(0));
+    assert_eq!(unnamed_term!(0).eta_expand(1), unnamed_term!(λ 1 0));
+    assert_eq!(unnamed_term!(0).eta_expand(2), unnamed_term!(λ λ 2 1 0));
+    assert_eq!(
+        unnamed_term!(λ 0 1).eta_expand(1),
+        unnamed_term!(λ (λ 0 2) 0)
+    );
+
+    let term = unnamed_term!(3 (λ 0 1));
+    assert_eq!(term.eta_expand(3).eta_normalize(), term);
+}
+
+#[test]
+fn reduce_step_with_eta() {
+    let term = unnamed_term!(λ (λ 0) 1 0);
+    assert_eq!(
+        term.reduce_step(Strategy::NormalOrder, true),
+        Some(unnamed_term!((λ 0) 0))
+    );
+    assert_eq!(
+        term.reduce_step(Strategy::ApplicativeOrder, true),
+        Some(unnamed_term!(λ 1 0))
+    );
+    assert_eq!(
+        term.reduce_step(Strategy::NormalOrder, false),
+        Some(unnamed_term!(λ 1 0))
+    );
+
+    let term = unnamed_term!(λ 1 0);
+    assert_eq!(term.reduce_step(Strategy::CallByName, true), Some(unnamed_term!(0)));
+    assert_eq!(term.reduce_step(Strategy::CallByValue, true), Some(unnamed_term!(0)));
+    assert_eq!(term.reduce_step(Strategy::CallByName, false), None);
+
+    let term = unnamed_term!(λ λ (λ 0) 2 1 0);
+    assert_eq!(term.normalize(Strategy::NormalOrder, 10, true), Some(unnamed_term!(0)));
+}
@@ -0,0 +1,71 @@
+use super::Term;
+use std::collections::HashSet;
+
+impl Term {
+    /// Извършва една стъпка на главна редукция - свива главния редекс, т.е.
+    /// редекса `(λ M) N` в терм от вида `λ ... λ (λ M) N N1 ... Nk`.
+    ///
+    /// Връща `None`, ако термът е в глава нормална форма
+    /// `λ ... λ x M1 ... Mk`.
+    pub fn head_reduce_step(&self) -> Option<Term> {
+        match self {
+            Term::Var(_) => None,
+            Term::Lambda(t) => t.head_reduce_step().map(Term::lambda),
+            Term::Apply(t1, t2) => self
+                .contract()
+                .or_else(|| t1.head_reduce_step().map(|t| Term::apply(t, (**t2).clone()))),
+        }
+    }
+
+    /// Редуцира терма с главна редукция до глава нормална форма, но не
+    /// повече от `fuel` стъпки.
+    ///
+    /// Връща `None`, ако стъпките не са стигнали.
+    pub fn head_normal_form(&self, fuel: usize) -> Option<Term> {
+        let mut term = self.clone();
+
+        for _ in 0..fuel {
+            match term.head_reduce_step() {
+                Some(next) => term = next,
+                None => return Some(term),
+            }
+        }
+
+        term.head_reduce_step().map_or(Some(term), |_| None)
+    }
+
+    /// Проверява дали термът е разрешим, т.е. дали има глава нормална форма.
+    ///
+    /// Връща `Some(true)`, ако за не повече от `fuel` стъпки главната
+    /// редукция достигне глава нормална форма, и `Some(false)`, ако тя
+    /// се зацикли (например при `Ω`), което доказва, че термът е
+    /// неразрешим. В останалите случаи въпросът остава открит и се връща
+    /// `None`.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::unnamed_term;
+    ///
+    /// assert_eq!(unnamed_term!((λ 0 0) (λ 0)).is_solvable(10), Some(true));
+    /// assert_eq!(unnamed_term!((λ 0 0) (λ 0 0)).is_solvable(10), Some(false));
+    /// assert_eq!(unnamed_term!((λ 0 0 0) (λ 0 0 0)).is_solvable(10), None);
+    /// ```
+    pub fn is_solvable(&self, fuel: usize) -> Option<bool> {
+        let mut seen = HashSet::new();
+        let mut term = self.clone();
+
+        for _ in 0..=fuel {
+            match term.head_reduce_step() {
+                None => return Some(true),
+                Some(_) if !seen.insert(term.clone()) => return Some(false),
+                Some(next) => term = next,
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,46 @@
+use crate::unnamed::Term;
+use crate::unnamed_term;
+
+#[test]
+fn head_reduce_step() {
+    assert_eq!(unnamed_term!(0 ((λ 0) 1)).head_reduce_step(), None);
+    assert_eq!(unnamed_term!(λ λ 1 ((λ 0) 0)).head_reduce_step(), None);
+
+    assert_eq!(
+        unnamed_term!(λ (λ 0 1) 2 ((λ 0) 3)).head_reduce_step(),
+        Some(unnamed_term!(λ 2 0 ((λ 0) 3)))
+    );
+    assert_eq!(
+        unnamed_term!((λ λ 1) 0 ((λ 0) 0)).head_reduce_step(),
+        Some(unnamed_term!((λ 1) ((λ 0) 0)))
+    );
+}
+
+#[test]
+fn head_normal_form() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+
+    // λ x. x Ω няма нормална форма, но е в глава нормална форма.
+    let term = Term::lambda(Term::apply(Term::var(0), omega.clone()));
+    assert_eq!(term.head_normal_form(0), Some(term.clone()));
+    assert_eq!(term.normalize(crate::unnamed::Strategy::NormalOrder, 100, false), None);
+
+    let term = Term::apply(unnamed_term!(λ λ 0 1), omega.clone());
+    assert_eq!(term.head_normal_form(1), Some(Term::lambda(Term::apply(Term::var(0), omega))));
+    assert_eq!(term.head_normal_form(0), None);
+}
+
+#[test]
+fn is_solvable() {
+    assert_eq!(unnamed_term!(0).is_solvable(0), Some(true));
+    assert_eq!(unnamed_term!((λ 0) 1).is_solvable(0), None);
+    assert_eq!(unnamed_term!((λ 0) 1).is_solvable(1), Some(true));
+
+    // λ x. Ω и Ω Ω са неразрешими.
+    assert_eq!(unnamed_term!(λ (λ 0 0) (λ 0 0)).is_solvable(10), Some(false));
+    assert_eq!(unnamed_term!((λ 0 0) (λ 0 0) ((λ 0 0) (λ 0 0))).is_solvable(10), Some(false));
+
+    // Y = λ f. (λ x. f (x x)) (λ x. f (x x)) е разрешим.
+    let y = unnamed_term!(λ (λ 1 (0 0)) (λ 1 (0 0)));
+    assert_eq!(y.is_solvable(10), Some(true));
+}
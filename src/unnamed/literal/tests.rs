@@ -1,5 +1,4 @@
 use crate::unnamed::Term;
-use crate::unnamed_term;
 
 #[test]
 fn macro_var() {
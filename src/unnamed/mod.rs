@@ -1,4 +1,12 @@
+mod eta;
+mod head;
 mod literal;
+mod reduction;
+mod statistics;
 mod term;
+mod zipper;
 
+pub use self::reduction::{Convertibility, Strategy};
+pub use self::statistics::{Collector, Statistics};
 pub use self::term::Term;
+pub use self::zipper::Zipper;
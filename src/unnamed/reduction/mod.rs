@@ -0,0 +1,379 @@
+use super::{Collector, Term};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Стратегия за β-редукция.
+///
+/// Определя кой редекс се свива на всяка стъпка.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Strategy {
+    /// Най-левият най-външен редекс. Достига нормалната форма, ако такава
+    /// съществува.
+    NormalOrder,
+    /// Най-левият най-вътрешен редекс.
+    ApplicativeOrder,
+    /// Най-левият най-външен редекс, без редукция под λ и в аргументите.
+    /// Спира в слаба глава нормална форма.
+    CallByName,
+    /// Аргументите се оценяват преди контракцията, без редукция под λ.
+    CallByValue,
+}
+
+/// Разпознава имената `normal`, `applicative`, `name` и `value`.
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "normal" => Ok(Strategy::NormalOrder),
+            "applicative" => Ok(Strategy::ApplicativeOrder),
+            "name" => Ok(Strategy::CallByName),
+            "value" => Ok(Strategy::CallByValue),
+            _ => Err(format!("непозната стратегия '{}'", s)),
+        }
+    }
+}
+
+/// Принтира името, което се разпознава от `FromStr`.
+impl Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Strategy::NormalOrder => "normal",
+            Strategy::ApplicativeOrder => "applicative",
+            Strategy::CallByName => "name",
+            Strategy::CallByValue => "value",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Резултат от проверка за конвертируемост на два терма.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Convertibility {
+    /// Термовете са конвертируеми - съвпадат или имат една и съща нормална
+    /// форма.
+    Equal,
+    /// Термовете имат различни нормални форми, следователно не са
+    /// конвертируеми.
+    DifferentNormalForms,
+    /// Поне за единия терм не е достигната нормална форма в рамките на
+    /// зададения брой стъпки.
+    Undecided,
+}
+
+impl Strategy {
+    /// Всички стратегии.
+    pub const ALL: [Strategy; 4] = [
+        Strategy::NormalOrder,
+        Strategy::ApplicativeOrder,
+        Strategy::CallByName,
+        Strategy::CallByValue,
+    ];
+}
+
+impl Term {
+    /// Извършва β-контракция, ако термът е редекс `(λ M) N`.
+    ///
+    /// Връща `M[0 -> N]`, като индексите на свободните променливи се
+    /// коригират, защото абстракцията изчезва. Ако термът не е редекс,
+    /// връща `None`.
+    pub fn contract(&self) -> Option<Term> {
+        match self {
+            Term::Apply(t1, t2) => match &**t1 {
+                Term::Lambda(body) => Some(body.substitute(0, &t2.raise(0)).lower(0)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Връща всички термове, които се получават от терма с една β-редукция.
+    ///
+    /// Всеки елемент съответства на един редекс. Редексите са подредени по
+    /// позицията си - отляво надясно и отвън навътре.
+    pub fn reducts(&self) -> Vec<Term> {
+        let mut result = Vec::new();
+
+        if let Some(term) = self.contract() {
+            result.push(term);
+        }
+
+        match self {
+            Term::Var(_) => {},
+            Term::Apply(t1, t2) => {
+                result.extend(t1.reducts().into_iter().map(|t| Term::apply(t, (**t2).clone())));
+                result.extend(t2.reducts().into_iter().map(|t| Term::apply((**t1).clone(), t)));
+            },
+            Term::Lambda(t) => {
+                result.extend(t.reducts().into_iter().map(Term::lambda));
+            },
+        }
+
+        result
+    }
+
+    /// Извършва една стъпка на β-редукция според стратегията `strategy`.
+    ///
+    /// Ако `eta` е `true`, се извършва βη-редукция - всяка абстракция, до която
+    /// стратегията стига, се проверява и за η-редекс. Нормалната стратегия
+    /// свива η-редекса преди редексите в тялото му, апликативната - след тях.
+    ///
+    /// Връща `None`, ако според стратегията няма редекс за свиване.
+    pub fn reduce_step(&self, strategy: Strategy, eta: bool) -> Option<Term> {
+        self.reduce_step_with(strategy, eta, &mut ())
+    }
+
+    /// Като `reduce_step`, но съобщава на `collector` за свития редекс.
+    pub fn reduce_step_with(
+        &self,
+        strategy: Strategy,
+        eta: bool,
+        collector: &mut dyn Collector,
+    ) -> Option<Term> {
+        use Strategy::*;
+
+        let eta_contract = || if eta { self.eta_contract() } else { None };
+
+        match (self, strategy) {
+            (Term::Var(_), _) => None,
+            (Term::Lambda(t), NormalOrder) => eta_contract()
+                .or_else(|| t.reduce_step_with(strategy, eta, collector).map(Term::lambda)),
+            (Term::Lambda(t), ApplicativeOrder) => t
+                .reduce_step_with(strategy, eta, collector)
+                .map(Term::lambda)
+                .or_else(eta_contract),
+            (Term::Lambda(_), CallByName) | (Term::Lambda(_), CallByValue) => eta_contract(),
+            (Term::Apply(t1, t2), NormalOrder) => self
+                .contract_with(collector)
+                .or_else(|| {
+                    t1.reduce_step_with(strategy, eta, collector)
+                        .map(|t| Term::apply(t, (**t2).clone()))
+                })
+                .or_else(|| {
+                    t2.reduce_step_with(strategy, eta, collector)
+                        .map(|t| Term::apply((**t1).clone(), t))
+                }),
+            (Term::Apply(t1, t2), CallByName) => self.contract_with(collector).or_else(|| {
+                t1.reduce_step_with(strategy, eta, collector)
+                    .map(|t| Term::apply(t, (**t2).clone()))
+            }),
+            (Term::Apply(t1, t2), ApplicativeOrder) | (Term::Apply(t1, t2), CallByValue) => t1
+                .reduce_step_with(strategy, eta, collector)
+                .map(|t| Term::apply(t, (**t2).clone()))
+                .or_else(|| {
+                    t2.reduce_step_with(strategy, eta, collector)
+                        .map(|t| Term::apply((**t1).clone(), t))
+                })
+                .or_else(|| self.contract_with(collector)),
+        }
+    }
+
+    fn contract_with(&self, collector: &mut dyn Collector) -> Option<Term> {
+        if let Term::Apply(t1, t2) = self {
+            if let Term::Lambda(body) = &**t1 {
+                collector.contraction(body, t2);
+            }
+        }
+
+        self.contract()
+    }
+
+    /// Редуцира терма според стратегията `strategy`, докато има редекси за
+    /// свиване, но не повече от `fuel` стъпки. Параметърът `eta` е като при
+    /// `reduce_step`.
+    ///
+    /// Връща `None`, ако стъпките не са стигнали.
+    pub fn normalize(&self, strategy: Strategy, fuel: usize, eta: bool) -> Option<Term> {
+        self.normalize_with(strategy, fuel, eta, &mut ())
+    }
+
+    /// Като `normalize`, но съобщава на `collector` за всеки терм от
+    /// редукционната редица и за всеки свит редекс.
+    pub fn normalize_with(
+        &self,
+        strategy: Strategy,
+        fuel: usize,
+        eta: bool,
+        collector: &mut dyn Collector,
+    ) -> Option<Term> {
+        let mut term = self.clone();
+        let mut fuel = fuel;
+        collector.term(&term);
+
+        loop {
+            if fuel == 0 {
+                return term.reduce_step(strategy, eta).map_or(Some(term), |_| None);
+            }
+
+            match term.reduce_step_with(strategy, eta, collector) {
+                None => return Some(term),
+                Some(next) => {
+                    collector.term(&next);
+                    term = next;
+                    fuel -= 1;
+                },
+            }
+        }
+    }
+
+    /// Проверява дали два терма са β-конвертируеми (или βη-конвертируеми,
+    /// ако `eta` е `true`).
+    ///
+    /// Термовете се нормализират с нормалната стратегия, като за всеки от тях
+    /// се правят най-много `fuel` стъпки, и нормалните им форми се сравняват.
+    /// Съвпадащи термове са конвертируеми и без нормализиране.
+    pub fn convertible(&self, other: &Term, fuel: usize, eta: bool) -> Convertibility {
+        if self == other {
+            return Convertibility::Equal;
+        }
+
+        let normal_forms = (
+            self.normalize(Strategy::NormalOrder, fuel, eta),
+            other.normalize(Strategy::NormalOrder, fuel, eta),
+        );
+
+        match normal_forms {
+            (Some(t1), Some(t2)) if t1 == t2 => Convertibility::Equal,
+            (Some(_), Some(_)) => Convertibility::DifferentNormalForms,
+            _ => Convertibility::Undecided,
+        }
+    }
+
+    /// Проверява дали променливата с индекс `var` е свободна в терма.
+    pub fn is_free(&self, var: usize) -> bool {
+        match self {
+            Term::Var(x) => *x == var,
+            Term::Apply(t1, t2) => t1.is_free(var) || t2.is_free(var),
+            Term::Lambda(t) => t.is_free(var + 1),
+        }
+    }
+
+    /// Проверява дали всички свободни променливи на терма са с индекси
+    /// по-малки от `context`.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::unnamed_term;
+    ///
+    /// assert!(unnamed_term!(λ 0 1).is_closed_in(1));
+    /// assert!(!unnamed_term!(λ 0 1).is_closed_in(0));
+    /// ```
+    pub fn is_closed_in(&self, context: usize) -> bool {
+        match self {
+            Term::Var(x) => *x < context,
+            Term::Apply(t1, t2) => t1.is_closed_in(context) && t2.is_closed_in(context),
+            Term::Lambda(t) => t.is_closed_in(context + 1),
+        }
+    }
+
+    /// Търси общ редукт на два терма.
+    ///
+    /// Редукциите на двата терма се обхождат в ширина, но не повече от `fuel`
+    /// стъпки за всеки от тях. Ако бъде намерен общ редукт `P`, се връщат
+    /// двете редукционни редици `self ->> P` и `other ->> P`, като всяка
+    /// започва с началния терм и завършва с `P`.
+    ///
+    /// По теоремата на Чърч-Росер общ редукт съществува точно когато двата
+    /// терма са β-конвертируеми, но може да е нужен повече от `fuel` стъпки.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::unnamed_term;
+    ///
+    /// let term = unnamed_term!((λ 0 0) ((λ 0) 1));
+    /// let reducts = term.reducts();
+    ///
+    /// let (left, right) = reducts[0].common_reduct(&reducts[1], 10).unwrap();
+    /// assert_eq!(left.last(), Some(&unnamed_term!(1 1)));
+    /// assert_eq!(right.last(), Some(&unnamed_term!(1 1)));
+    /// ```
+    pub fn common_reduct(&self, other: &Term, fuel: usize) -> Option<(Vec<Term>, Vec<Term>)> {
+        if self == other {
+            return Some((vec![self.clone()], vec![other.clone()]));
+        }
+
+        let mut left = ReductionGraph::new(self.clone());
+        let mut right = ReductionGraph::new(other.clone());
+
+        for _ in 0..fuel {
+            if let Some(term) = left.expand().into_iter().find(|t| right.contains(t)) {
+                return Some((left.path(&term), right.path(&term)));
+            }
+
+            if let Some(term) = right.expand().into_iter().find(|t| left.contains(t)) {
+                return Some((left.path(&term), right.path(&term)));
+            }
+
+            if left.is_exhausted() && right.is_exhausted() {
+                break;
+            }
+        }
+
+        None
+    }
+}
+
+/// Частично обходен граф на редукциите на терм.
+///
+/// За всеки достигнат терм пази терма, от който е получен за пръв път, така
+/// че да може да се възстанови редукционната редица до него.
+struct ReductionGraph {
+    parents: HashMap<Term, Option<Term>>,
+    frontier: Vec<Term>,
+}
+
+impl ReductionGraph {
+    fn new(start: Term) -> Self {
+        let mut parents = HashMap::new();
+        parents.insert(start.clone(), None);
+
+        ReductionGraph {
+            parents,
+            frontier: vec![start],
+        }
+    }
+
+    fn contains(&self, term: &Term) -> bool {
+        self.parents.contains_key(term)
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.frontier.is_empty()
+    }
+
+    /// Обхожда още едно ниво на графа и връща новодостигнатите термове.
+    fn expand(&mut self) -> Vec<Term> {
+        let mut next = Vec::new();
+
+        for term in std::mem::take(&mut self.frontier) {
+            for reduct in term.reducts() {
+                if !self.parents.contains_key(&reduct) {
+                    self.parents.insert(reduct.clone(), Some(term.clone()));
+                    next.push(reduct);
+                }
+            }
+        }
+
+        self.frontier = next.clone();
+        next
+    }
+
+    /// Връща редукционната редица от началния терм до `term`.
+    fn path(&self, term: &Term) -> Vec<Term> {
+        let mut path = vec![term.clone()];
+
+        while let Some(Some(parent)) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests;
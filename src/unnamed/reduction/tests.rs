@@ -0,0 +1,180 @@
+use crate::unnamed::{Convertibility, Strategy};
+use crate::unnamed_term;
+
+#[test]
+fn contract() {
+    assert_eq!(unnamed_term!(0).contract(), None);
+    assert_eq!(unnamed_term!(λ 0).contract(), None);
+    assert_eq!(unnamed_term!(0 (λ 0)).contract(), None);
+
+    assert_eq!(unnamed_term!((λ 0) 1).contract(), Some(unnamed_term!(1)));
+    assert_eq!(unnamed_term!((λ 1) 2).contract(), Some(unnamed_term!(0)));
+    assert_eq!(unnamed_term!((λ 0 2) 5).contract(), Some(unnamed_term!(5 1)));
+
+    assert_eq!(
+        unnamed_term!((λ λ 1) 0).contract(),
+        Some(unnamed_term!(λ 1))
+    );
+    assert_eq!(
+        unnamed_term!((λ λ 1 0) (λ 0 1)).contract(),
+        Some(unnamed_term!(λ (λ 0 2) 0))
+    );
+}
+
+#[test]
+fn reducts() {
+    assert_eq!(unnamed_term!(0 1).reducts(), vec![]);
+    assert_eq!(unnamed_term!(λ (λ 0) 0).reducts(), vec![unnamed_term!(λ 0)]);
+
+    assert_eq!(
+        unnamed_term!((λ 0 0) ((λ 0) 1)).reducts(),
+        vec![
+            unnamed_term!(((λ 0) 1) ((λ 0) 1)),
+            unnamed_term!((λ 0 0) 1),
+        ]
+    );
+
+    assert_eq!(
+        unnamed_term!(((λ 0) 1) ((λ 0) 2)).reducts(),
+        vec![unnamed_term!(1 ((λ 0) 2)), unnamed_term!(((λ 0) 1) 2)]
+    );
+}
+
+#[test]
+fn common_reduct() {
+    let term = unnamed_term!(0 1);
+    assert_eq!(
+        term.common_reduct(&term, 0),
+        Some((vec![term.clone()], vec![term.clone()]))
+    );
+
+    assert_eq!(
+        unnamed_term!((λ 0) 1).common_reduct(&unnamed_term!(1), 1),
+        Some((vec![unnamed_term!((λ 0) 1), unnamed_term!(1)], vec![unnamed_term!(1)]))
+    );
+
+    let left = unnamed_term!(((λ 0) 1) ((λ 0) 1));
+    let right = unnamed_term!((λ 0 0) 1);
+    assert_eq!(
+        left.common_reduct(&right, 2),
+        Some((
+            vec![left.clone(), unnamed_term!(1 ((λ 0) 1)), unnamed_term!(1 1)],
+            vec![right.clone(), unnamed_term!(1 1)],
+        ))
+    );
+    assert_eq!(left.common_reduct(&right, 1), None);
+}
+
+#[test]
+fn common_reduct_not_found() {
+    assert_eq!(unnamed_term!(0).common_reduct(&unnamed_term!(1), 10), None);
+    assert_eq!(
+        unnamed_term!((λ 0) 0).common_reduct(&unnamed_term!((λ 0) 1), 10),
+        None
+    );
+
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+    assert_eq!(omega.common_reduct(&unnamed_term!(λ 0), 10), None);
+}
+
+#[test]
+fn reduce_step() {
+    let term = unnamed_term!((λ 0) ((λ 0) 1));
+    assert_eq!(term.reduce_step(Strategy::NormalOrder, false), Some(unnamed_term!((λ 0) 1)));
+    assert_eq!(term.reduce_step(Strategy::ApplicativeOrder, false), Some(unnamed_term!((λ 0) 1)));
+    assert_eq!(term.reduce_step(Strategy::CallByName, false), Some(unnamed_term!((λ 0) 1)));
+    assert_eq!(term.reduce_step(Strategy::CallByValue, false), Some(unnamed_term!((λ 0) 1)));
+
+    let term = unnamed_term!((λ λ 1) ((λ 0) 1));
+    assert_eq!(term.reduce_step(Strategy::NormalOrder, false), Some(unnamed_term!(λ (λ 0) 2)));
+    assert_eq!(term.reduce_step(Strategy::ApplicativeOrder, false), Some(unnamed_term!((λ λ 1) 1)));
+    assert_eq!(term.reduce_step(Strategy::CallByName, false), Some(unnamed_term!(λ (λ 0) 2)));
+    assert_eq!(term.reduce_step(Strategy::CallByValue, false), Some(unnamed_term!((λ λ 1) 1)));
+
+    let term = unnamed_term!(λ (λ 0) 0);
+    assert_eq!(term.reduce_step(Strategy::NormalOrder, false), Some(unnamed_term!(λ 0)));
+    assert_eq!(term.reduce_step(Strategy::ApplicativeOrder, false), Some(unnamed_term!(λ 0)));
+    assert_eq!(term.reduce_step(Strategy::CallByName, false), None);
+    assert_eq!(term.reduce_step(Strategy::CallByValue, false), None);
+
+    let term = unnamed_term!(0 ((λ 0) 1));
+    assert_eq!(term.reduce_step(Strategy::NormalOrder, false), Some(unnamed_term!(0 1)));
+    assert_eq!(term.reduce_step(Strategy::CallByName, false), None);
+    assert_eq!(term.reduce_step(Strategy::CallByValue, false), Some(unnamed_term!(0 1)));
+}
+
+#[test]
+fn normalize() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+    assert_eq!(omega.normalize(Strategy::NormalOrder, 100, false), None);
+
+    let term = unnamed_term!((λ λ 0) ((λ 0 0) (λ 0 0)));
+    assert_eq!(term.normalize(Strategy::NormalOrder, 1, false), Some(unnamed_term!(λ 0)));
+    assert_eq!(term.normalize(Strategy::NormalOrder, 0, false), None);
+    assert_eq!(term.normalize(Strategy::ApplicativeOrder, 100, false), None);
+    assert_eq!(term.normalize(Strategy::CallByName, 100, false), Some(unnamed_term!(λ 0)));
+    assert_eq!(term.normalize(Strategy::CallByValue, 100, false), None);
+
+    let term = unnamed_term!((λ λ 1 0) (λ 0) 2);
+    assert_eq!(term.normalize(Strategy::NormalOrder, 3, false), Some(unnamed_term!(2)));
+    assert_eq!(term.normalize(Strategy::ApplicativeOrder, 3, false), Some(unnamed_term!(2)));
+}
+
+#[test]
+fn is_free() {
+    assert!(unnamed_term!(0).is_free(0));
+    assert!(!unnamed_term!(1).is_free(0));
+    assert!(!unnamed_term!(λ 0).is_free(0));
+    assert!(unnamed_term!(λ 1).is_free(0));
+    assert!(unnamed_term!(1 (λ 2)).is_free(1));
+}
+
+#[test]
+fn is_closed_in() {
+    assert!(unnamed_term!(λ 0).is_closed_in(0));
+    assert!(!unnamed_term!(0).is_closed_in(0));
+    assert!(unnamed_term!(1 (λ 2)).is_closed_in(2));
+    assert!(!unnamed_term!(1 (λ 2)).is_closed_in(1));
+}
+
+#[test]
+fn convertible() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+
+    assert_eq!(
+        unnamed_term!((λ 0) 1).convertible(&unnamed_term!(1), 10, false),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        unnamed_term!((λ 0) 1).convertible(&unnamed_term!(2), 10, false),
+        Convertibility::DifferentNormalForms
+    );
+    assert_eq!(omega.convertible(&omega, 10, false), Convertibility::Equal);
+    assert_eq!(omega.convertible(&omega, 0, false), Convertibility::Equal);
+    assert_eq!(
+        omega.convertible(&unnamed_term!(λ 0 0 0), 10, false),
+        Convertibility::Undecided
+    );
+
+    assert_eq!(
+        unnamed_term!(λ 1 0).convertible(&unnamed_term!(0), 10, false),
+        Convertibility::DifferentNormalForms
+    );
+    assert_eq!(
+        unnamed_term!(λ 1 0).convertible(&unnamed_term!(0), 10, true),
+        Convertibility::Equal
+    );
+    assert_eq!(
+        unnamed_term!(λ 0 0).convertible(&unnamed_term!(0), 10, true),
+        Convertibility::DifferentNormalForms
+    );
+}
+
+#[test]
+fn strategy_names() {
+    for &strategy in &Strategy::ALL {
+        assert_eq!(strategy.to_string().parse(), Ok(strategy));
+    }
+
+    assert!("lazy".parse::<Strategy>().is_err());
+}
@@ -0,0 +1,82 @@
+use super::Term;
+use crate::lambda;
+use crate::visit::{self, TermVisitor};
+
+/// Събира информация по време на редукция. Подава се на
+/// `Term::reduce_step_with` и `Term::normalize_with`.
+///
+/// И двата метода по подразбиране не правят нищо, така че всеки събирач
+/// имплементира само нужните му.
+pub trait Collector {
+    /// Извиква се при всяка β-контракция на редекс `(λ body) arg`.
+    fn contraction(&mut self, _body: &Term, _arg: &Term) {}
+
+    /// Извиква се за всеки терм от редукционната редица, включително
+    /// началния.
+    fn term(&mut self, _term: &Term) {}
+}
+
+/// Събирач, който не прави нищо.
+impl Collector for () {}
+
+/// Статистика за цената на една редукция.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Statistics {
+    /// Брой β-стъпки.
+    pub steps: usize,
+    /// Най-голям размер (вж. `Term::size`) на терм от редукционната редица.
+    pub max_size: usize,
+    /// Общ брой възли, построени при субституциите. Контракцията на
+    /// `(λ M) N` струва `|M| + k|N|`, където `k` е броят на срещанията на
+    /// свързаната променлива в `M`.
+    pub substitution_work: usize,
+}
+
+impl Collector for Statistics {
+    fn contraction(&mut self, body: &Term, arg: &Term) {
+        self.steps += 1;
+        self.substitution_work += body.size() + body.occurrences(0) * arg.size();
+    }
+
+    fn term(&mut self, term: &Term) {
+        self.max_size = self.max_size.max(term.size());
+    }
+}
+
+impl Term {
+    /// Връща размера на терма - броя на възлите в синтактичното му дърво.
+    ///
+    /// # Пример
+    ///
+    /// ```
+    /// use hw::unnamed_term;
+    ///
+    /// assert_eq!(unnamed_term!(λ 0 1).size(), 4);
+    /// ```
+    pub fn size(&self) -> usize {
+        lambda::size(self)
+    }
+
+    /// Връща броя на срещанията на променливата с индекс `var`.
+    pub fn occurrences(&self, var: usize) -> usize {
+        struct Occurrences {
+            var: usize,
+            count: usize,
+        }
+
+        impl TermVisitor<Term> for Occurrences {
+            fn visit_var(&mut self, x: &usize, depth: &usize) {
+                if *x == self.var + depth {
+                    self.count += 1;
+                }
+            }
+        }
+
+        let mut occurrences = Occurrences { var, count: 0 };
+        visit::walk(self, &mut occurrences);
+        occurrences.count
+    }
+}
+
+#[cfg(test)]
+mod tests;
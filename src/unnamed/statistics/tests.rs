@@ -0,0 +1,74 @@
+use crate::unnamed::{Collector, Statistics, Strategy, Term};
+use crate::unnamed_term;
+
+#[test]
+fn size() {
+    assert_eq!(unnamed_term!(0).size(), 1);
+    assert_eq!(unnamed_term!(0 1).size(), 3);
+    assert_eq!(unnamed_term!((λ 0 0) (λ 0 0)).size(), 9);
+}
+
+#[test]
+fn occurrences() {
+    assert_eq!(unnamed_term!(0 1 0).occurrences(0), 2);
+    assert_eq!(unnamed_term!(λ 0 1 (λ 2)).occurrences(0), 2);
+    assert_eq!(unnamed_term!(λ 0).occurrences(0), 0);
+}
+
+#[test]
+fn statistics() {
+    // (λ x. x x) ((λ y. y) z)
+    let term = unnamed_term!((λ 0 0) ((λ 0) 0));
+
+    let mut statistics = Statistics::default();
+    let result = term.normalize_with(Strategy::NormalOrder, 10, false, &mut statistics);
+    assert_eq!(result, Some(unnamed_term!(0 0)));
+    assert_eq!(
+        statistics,
+        Statistics {
+            steps: 3,
+            max_size: 9,
+            substitution_work: (3 + 2 * 4) + (1 + 1) + (1 + 1),
+        }
+    );
+
+    let mut statistics = Statistics::default();
+    let result = term.normalize_with(Strategy::ApplicativeOrder, 10, false, &mut statistics);
+    assert_eq!(result, Some(unnamed_term!(0 0)));
+    assert_eq!(
+        statistics,
+        Statistics {
+            steps: 2,
+            max_size: 9,
+            substitution_work: 2 + 5,
+        }
+    );
+}
+
+#[test]
+fn statistics_without_normal_form() {
+    let omega = unnamed_term!((λ 0 0) (λ 0 0));
+
+    let mut statistics = Statistics::default();
+    assert_eq!(omega.normalize_with(Strategy::NormalOrder, 5, false, &mut statistics), None);
+    assert_eq!(statistics.steps, 5);
+    assert_eq!(statistics.max_size, omega.size());
+}
+
+#[test]
+fn custom_collector() {
+    struct Trace(Vec<Term>);
+
+    impl Collector for Trace {
+        fn term(&mut self, term: &Term) {
+            self.0.push(term.clone());
+        }
+    }
+
+    let mut trace = Trace(Vec::new());
+    unnamed_term!((λ 0) ((λ 0) 1)).normalize_with(Strategy::CallByValue, 10, false, &mut trace);
+    assert_eq!(
+        trace.0,
+        vec![unnamed_term!((λ 0) ((λ 0) 1)), unnamed_term!((λ 0) 1), unnamed_term!(1)]
+    );
+}
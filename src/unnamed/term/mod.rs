@@ -1,9 +1,16 @@
+use crate::lambda::{self, LambdaTerm, View, ViewMut};
 use crate::named::Term as NamedTerm;
 use serde_derive::{Deserialize, Serialize};
-use std::fmt::{self, Display};
+use std::fmt::{self, Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::mem;
 
 /// Безименен ламбда терм
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Всички операции върху терма, включително `Clone`, `PartialEq`, `Hash`,
+/// `Debug` и `Drop`, използват явен стек, така че работят и за много дълбоки
+/// термове. Само сериализацията и десериализацията са рекурсивни.
+#[derive(Serialize, Deserialize)]
 pub enum Term {
     Var(usize),
     Apply(Box<Term>, Box<Term>),
@@ -29,61 +36,281 @@ impl Term {
     /// с кой индекс са заместени свободните променливи в оригиналния терм.
     pub fn from_named(named: &NamedTerm) -> (Self, Vec<String>) {
         let mut names = Vec::new();
-        let mut args = Vec::new();
 
-        let term = Self::from_named_inner(named, &mut args, &mut names);
+        let term = Self::from_named_with(named, &mut names);
         (term, names)
     }
 
+    /// Създава безименен терм от именуван, като използва вече съществуващ
+    /// контекст от имена.
+    ///
+    /// Свободните променливи, които не присъстват в `names`, се добавят в края
+    /// му. Така няколко терма могат да бъдат превърнати в общ контекст.
+    pub fn from_named_with(named: &NamedTerm, names: &mut Vec<String>) -> Self {
+        let mut args = Vec::new();
+        Self::from_named_inner(named, &mut args, names)
+    }
+
     fn from_named_inner(
         named: &NamedTerm,
         args: &mut Vec<String>,
         names: &mut Vec<String>,
     ) -> Self {
-        match named {
-            NamedTerm::Var(x) => match args.iter().rev().enumerate().find(|(_, arg)| **arg == *x) {
-                Some((index, _)) => Self::var(index),
-                None => match names.iter().position(|name| name == x) {
-                    Some(index) => Self::var(index),
-                    None => {
-                        names.push(x.clone());
-                        Self::var(args.len() + names.len() - 1)
-                    },
+        enum Frame<'a> {
+            Visit(&'a NamedTerm),
+            Apply,
+            Lambda,
+        }
+
+        let mut frames = vec![Frame::Visit(named)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(NamedTerm::Var(x)) => {
+                    let index = match args.iter().rev().position(|arg| arg == x) {
+                        Some(index) => index,
+                        None => match names.iter().position(|name| name == x) {
+                            Some(index) => args.len() + index,
+                            None => {
+                                names.push(x.clone());
+                                args.len() + names.len() - 1
+                            },
+                        },
+                    };
+
+                    results.push(Self::var(index));
                 },
-            },
-            NamedTerm::Apply(t1, t2) => Self::apply(
-                Self::from_named_inner(t1, args, names),
-                Self::from_named_inner(t2, args, names),
-            ),
-            NamedTerm::Lambda(x, t) => {
-                args.push(x.clone());
-                let result = Self::lambda(Self::from_named_inner(t, args, names));
-                args.pop();
-                result
-            },
+                Frame::Visit(NamedTerm::Apply(t1, t2)) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2));
+                    frames.push(Frame::Visit(t1));
+                },
+                Frame::Visit(NamedTerm::Lambda(x, t)) => {
+                    args.push(x.clone());
+                    frames.push(Frame::Lambda);
+                    frames.push(Frame::Visit(t));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(Self::apply(t1, t2));
+                },
+                Frame::Lambda => {
+                    args.pop();
+                    let t = results.pop().unwrap();
+                    results.push(Self::lambda(t));
+                },
+            }
         }
+
+        results.pop().unwrap()
     }
 
     /// Изпълнява субституцията `term[var -> subs]`
     pub fn substitute(&self, var: usize, subs: &Term) -> Term {
-        use Term::*;
+        self.map_vars(|x, depth| {
+            if x == var + depth {
+                subs.shift(0, depth)
+            } else {
+                Term::var(x)
+            }
+        })
+    }
+
+    /// Увеличава с единица индексите на променливите, които са свободни
+    /// спрямо първите `from` абстракции.
+    pub(crate) fn raise(&self, from: usize) -> Term {
+        self.shift(from, 1)
+    }
+
+    /// Увеличава с `by` индексите на променливите, които са свободни
+    /// спрямо първите `from` абстракции.
+    fn shift(&self, from: usize, by: usize) -> Term {
+        self.map_vars(|x, depth| {
+            if x < from + depth {
+                Term::var(x)
+            } else {
+                Term::var(x.wrapping_add(by))
+            }
+        })
+    }
+
+    /// Намалява с единица индексите на променливите, които са свободни
+    /// спрямо първите `from` абстракции. Обратна операция на `raise`.
+    pub(crate) fn lower(&self, from: usize) -> Term {
+        self.map_vars(|x, depth| {
+            if x < from + depth {
+                Term::var(x)
+            } else {
+                Term::var(x.wrapping_sub(1))
+            }
+        })
+    }
+
+    /// Копира терма, като заменя всяка променлива `x`, намираща се под
+    /// `depth` абстракции, с `f(x, depth)`.
+    fn map_vars<F>(&self, mut f: F) -> Term
+    where
+        F: FnMut(usize, usize) -> Term,
+    {
+        enum Frame<'a> {
+            Visit(&'a Term, usize),
+            Apply,
+            Lambda,
+        }
+
+        let mut frames = vec![Frame::Visit(self, 0)];
+        let mut results = Vec::new();
+
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Visit(Term::Var(x), depth) => results.push(f(*x, depth)),
+                Frame::Visit(Term::Apply(t1, t2), depth) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2, depth));
+                    frames.push(Frame::Visit(t1, depth));
+                },
+                Frame::Visit(Term::Lambda(t), depth) => {
+                    frames.push(Frame::Lambda);
+                    frames.push(Frame::Visit(t, depth + 1));
+                },
+                Frame::Apply => {
+                    let t2 = results.pop().unwrap();
+                    let t1 = results.pop().unwrap();
+                    results.push(Term::apply(t1, t2));
+                },
+                Frame::Lambda => {
+                    let t = results.pop().unwrap();
+                    results.push(Term::lambda(t));
+                },
+            }
+        }
+
+        results.pop().unwrap()
+    }
+
+    /// Премества децата на терма, които не са променливи, в `stack`, като
+    /// ги заменя с променливи.
+    fn take_children(&mut self, stack: &mut Vec<Term>) {
+        let mut take = |t: &mut Box<Term>| {
+            if let Term::Var(_) = **t {
+                return;
+            }
+
+            stack.push(mem::replace(&mut **t, Term::Var(0)));
+        };
+
+        match self {
+            Term::Var(_) => {},
+            Term::Apply(t1, t2) => {
+                take(t1);
+                take(t2);
+            },
+            Term::Lambda(t) => take(t),
+        }
+    }
+}
+
+impl Clone for Term {
+    fn clone(&self) -> Self {
+        self.map_vars(|x, _| Term::var(x))
+    }
+}
+
+impl PartialEq for Term {
+    fn eq(&self, other: &Self) -> bool {
+        let mut stack = vec![(self, other)];
+
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (Term::Var(x), Term::Var(y)) if x == y => {},
+                (Term::Apply(s1, s2), Term::Apply(t1, t2)) => {
+                    stack.push((s2, t2));
+                    stack.push((s1, t1));
+                },
+                (Term::Lambda(s), Term::Lambda(t)) => stack.push((s, t)),
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl Eq for Term {}
+
+impl Hash for Term {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut stack = vec![self];
+
+        while let Some(term) = stack.pop() {
+            mem::discriminant(term).hash(state);
 
-        fn raise(term: &Term, from: usize) -> Term {
             match term {
-                Var(x) if *x < from => Var(*x),
-                Var(x) => Var(x.wrapping_add(1)),
-                Apply(t1, t2) => Term::apply(raise(&t1, from), raise(&t2, from)),
-                Lambda(t) => Term::lambda(raise(&t, from + 1)),
+                Term::Var(x) => x.hash(state),
+                Term::Apply(t1, t2) => {
+                    stack.push(t2);
+                    stack.push(t1);
+                },
+                Term::Lambda(t) => stack.push(t),
             }
         }
+    }
+}
+
+impl Debug for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        lambda::fmt_debug(self, f, |_, _| Ok(()))
+    }
+}
+
+impl Drop for Term {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        self.take_children(&mut stack);
+
+        while let Some(mut term) = stack.pop() {
+            term.take_children(&mut stack);
+        }
+    }
+}
 
+impl LambdaTerm for Term {
+    type Var = usize;
+    type Binder = ();
+
+    fn var(var: usize) -> Self {
+        Term::var(var)
+    }
+
+    fn apply(t1: Term, t2: Term) -> Self {
+        Term::apply(t1, t2)
+    }
+
+    fn lambda(_: (), body: Term) -> Self {
+        Term::lambda(body)
+    }
+
+    fn view(&self) -> View<'_, Self> {
+        match self {
+            Term::Var(x) => View::Var(x),
+            Term::Apply(t1, t2) => View::Apply(t1, t2),
+            Term::Lambda(t) => View::Lambda(&(), t),
+        }
+    }
+
+    fn view_mut(&mut self) -> ViewMut<'_, Self> {
         match self {
-            Var(x) if *x == var => subs.clone(),
-            Var(x) => Var(*x),
-            Apply(t1, t2) => Term::apply(t1.substitute(var, subs), t2.substitute(var, subs)),
-            Lambda(t) => Term::lambda(t.substitute(var + 1, &raise(subs, 0))),
+            Term::Var(x) => ViewMut::Var(x),
+            Term::Apply(t1, t2) => ViewMut::Apply(t1, t2),
+            Term::Lambda(t) => ViewMut::Lambda(&(), t),
         }
     }
+
+    fn fmt_binder(_: &(), f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "λ ")
+    }
 }
 
 /// Формат за принтиране.
@@ -91,25 +318,7 @@ impl Term {
 /// Използва се от `println!("{}", ...)`
 impl Display for Term {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use Term::*;
-
-        match self {
-            Var(x) => write!(f, "{}", x),
-            Apply(t1, t2) => {
-                match **t1 {
-                    Var(_) | Apply(_, _) => write!(f, "{}", t1)?,
-                    _ => write!(f, "({})", t1)?,
-                }
-
-                write!(f, " ")?;
-
-                match **t2 {
-                    Var(_) => write!(f, "{}", t2),
-                    _ => write!(f, "({})", t2),
-                }
-            },
-            Lambda(t) => write!(f, "λ {}", t),
-        }
+        lambda::fmt(self, f)
     }
 }
 
@@ -70,6 +70,36 @@ fn conversion() {
     );
 }
 
+#[test]
+fn conversion_repeated_free_variable() {
+    assert_eq!(
+        Term::from_named(&term!(λ x. y y)),
+        (unnamed_term!(λ 1 1), vec![String::from("y")])
+    );
+    assert_eq!(
+        Term::from_named(&term!(y (λ x. y))),
+        (unnamed_term!(0 (λ 1)), vec![String::from("y")])
+    );
+}
+
+#[test]
+fn conversion_shared_context() {
+    let mut names = Vec::new();
+
+    assert_eq!(
+        Term::from_named_with(&term!(x y), &mut names),
+        unnamed_term!(0 1)
+    );
+    assert_eq!(
+        Term::from_named_with(&term!(λ z. y z w), &mut names),
+        unnamed_term!(λ 2 0 3)
+    );
+    assert_eq!(
+        names,
+        vec![String::from("x"), String::from("y"), String::from("w")]
+    );
+}
+
 #[test]
 fn substitute_no_match() {
     assert_eq!(
@@ -92,7 +122,7 @@ fn substitute_no_match() {
         unnamed_term!(λ 0 2)
     );
 
-    let max = usize::max_value();
+    let max = usize::MAX;
     assert_eq!(
         unnamed_term!(λ 0 max).substitute(0, &unnamed_term!(1)),
         unnamed_term!(λ 0 max)
@@ -172,3 +202,42 @@ fn substitute_with_lambda() {
         unnamed_term!(λ 0 (λ 0 2 3) 2)
     );
 }
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", unnamed_term!(0)), "Var(0)");
+    assert_eq!(
+        format!("{:?}", unnamed_term!(λ 0 (λ 2))),
+        "Lambda(Apply(Var(0), Lambda(Var(2))))"
+    );
+}
+
+/// Достатъчно дълбоко, за да препълни стека при рекурсивно обхождане.
+const DEPTH: usize = 100_000;
+
+#[test]
+fn deep_terms() {
+    let spine = (1..DEPTH).fold(Term::var(0), |t, i| Term::apply(t, Term::var(i % 3)));
+    let lambdas = (0..DEPTH).fold(Term::var(DEPTH), |t, _| Term::lambda(t));
+    let nested = (0..DEPTH).fold(Term::var(1), |t, _| Term::apply(Term::var(0), t));
+
+    for term in &[spine, lambdas, nested] {
+        let copy = term.clone();
+        assert_eq!(copy, *term);
+
+        let named = NamedTerm::from_unnamed(term);
+        assert_eq!(Term::from_named(&named).0, *term);
+
+        let substituted = term.substitute(0, &unnamed_term!(λ 0 1));
+        assert_eq!(substituted.raise(0).lower(0), substituted);
+        assert_ne!(substituted, *term);
+
+        let text = term.to_string();
+        assert!(text.len() > DEPTH);
+        assert!(format!("{:?}", term).len() > DEPTH);
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(copy);
+        assert!(set.contains(term));
+    }
+}
@@ -0,0 +1,30 @@
+use super::Term;
+use crate::path::{self, Path};
+use std::mem;
+
+impl Term {
+    /// Връща подтерма, до който води `path`, или `None`, ако пътят не
+    /// съществува в терма.
+    pub fn get(&self, path: &Path) -> Option<&Term> {
+        path::get(self, path)
+    }
+
+    /// Като `get`, но връща изменима референция.
+    pub fn get_mut(&mut self, path: &Path) -> Option<&mut Term> {
+        path::get_mut(self, path)
+    }
+
+    /// Заменя подтерма, до който води `path`, с `new` и връща стария.
+    ///
+    /// Индексите в `new` не се променят, т.е. те се отнасят за контекста на
+    /// подтерма. Връща `None` и не променя терма, ако пътят не съществува.
+    pub fn replace(&mut self, path: &Path, new: Term) -> Option<Term> {
+        self.get_mut(path).map(|term| mem::replace(term, new))
+    }
+}
+
+/// Безименен терм с фокус върху един от подтермовете му. Виж `path::Zipper`.
+pub type Zipper = path::Zipper<Term>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,79 @@
+use super::Zipper;
+use crate::path::{Path, Step};
+use crate::unnamed::Term;
+use crate::unnamed_term;
+
+#[test]
+fn get_and_replace() {
+    let mut term = unnamed_term!((λ 0 1) (λ λ 2));
+
+    let path = Path::from(vec![Step::Right, Step::Body, Step::Body]);
+    assert_eq!(term.get(&path), Some(&unnamed_term!(2)));
+    assert_eq!(term.get(&path.child(Step::Body)), None);
+
+    assert_eq!(term.replace(&path, unnamed_term!(0 1)), Some(unnamed_term!(2)));
+    assert_eq!(term, unnamed_term!((λ 0 1) (λ λ 0 1)));
+
+    assert_eq!(term.replace(&Path::from(vec![Step::Body]), unnamed_term!(0)), None);
+    assert_eq!(term, unnamed_term!((λ 0 1) (λ λ 0 1)));
+
+    let old = term.replace(&Path::new(), unnamed_term!(0));
+    assert_eq!(old, Some(unnamed_term!((λ 0 1) (λ λ 0 1))));
+    assert_eq!(term, unnamed_term!(0));
+}
+
+#[test]
+fn navigation() {
+    let term = unnamed_term!((λ 0 1) (λ λ 2));
+    let mut zipper = Zipper::new(term.clone());
+
+    assert!(!zipper.down(Step::Body));
+    assert_eq!(zipper.up(), None);
+
+    assert!(zipper.down(Step::Right));
+    assert!(zipper.down(Step::Body));
+    assert!(!zipper.down(Step::Left));
+    assert!(zipper.down(Step::Body));
+    assert_eq!(zipper.focus(), &unnamed_term!(2));
+    assert_eq!(zipper.depth(), 2);
+    assert_eq!(zipper.path().to_string(), "RBB");
+
+    assert_eq!(zipper.up(), Some(Step::Body));
+    assert_eq!(zipper.focus(), &unnamed_term!(λ 2));
+    assert_eq!(zipper.depth(), 1);
+
+    zipper.top();
+    assert!(zipper.path().is_empty());
+    assert_eq!(zipper.focus(), &term);
+
+    assert!(zipper.follow(&Path::from(vec![Step::Left, Step::Body, Step::Right])));
+    assert_eq!(zipper.focus(), &unnamed_term!(1));
+    assert_eq!(zipper.into_term(), term);
+}
+
+#[test]
+fn editing() {
+    let mut zipper = Zipper::new(unnamed_term!(0 (1 2)));
+
+    zipper.down(Step::Right);
+    zipper.down(Step::Left);
+    assert_eq!(zipper.replace(Term::lambda(Term::var(0))), unnamed_term!(1));
+
+    zipper.up();
+    *zipper.focus_mut() = Term::lambda(zipper.focus().clone());
+    assert_eq!(zipper.into_term(), unnamed_term!(0 (λ (λ 0) 2)));
+}
+
+#[test]
+fn deep_terms() {
+    let depth = 100_000;
+    let term = (0..depth).fold(Term::var(0), |t, _| Term::lambda(t));
+
+    let mut zipper = Zipper::new(term);
+    while zipper.down(Step::Body) {}
+    assert_eq!(zipper.depth(), depth);
+
+    zipper.replace(Term::var(depth - 1));
+    let term = zipper.into_term();
+    assert_eq!(term.get(&Path::from(vec![Step::Body; depth])), Some(&Term::var(depth - 1)));
+}
@@ -0,0 +1,212 @@
+//! Обхождане и свиване на термове със следене на обхвата.
+//!
+//! `walk` и `fold` обхождат терма с явен стек и поддържат обхвата на
+//! текущия връх - броя на абстракциите над него за безименните термове или
+//! свързаните имена за именуваните - така че анализите не трябва сами да
+//! следят свързващите абстракции.
+
+use crate::lambda::{LambdaTerm, View};
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+
+/// Представяне на термове, което знае как да следи обхвата.
+pub trait Scoped: LambdaTerm {
+    type Scope: Default;
+
+    /// Влиза в тялото на абстракция, свързваща `binder`.
+    fn enter(scope: &mut Self::Scope, binder: &Self::Binder);
+
+    /// Излиза от тялото на последната абстракция.
+    fn exit(scope: &mut Self::Scope);
+}
+
+/// Обхватът на безименен терм е броят на абстракциите над върха.
+impl Scoped for UnnamedTerm {
+    type Scope = usize;
+
+    fn enter(depth: &mut usize, _: &()) {
+        *depth += 1;
+    }
+
+    fn exit(depth: &mut usize) {
+        *depth -= 1;
+    }
+}
+
+/// Обхватът на именуван терм са имената на свързаните променливи, като
+/// най-вътрешната е последна.
+impl Scoped for NamedTerm {
+    type Scope = Vec<String>;
+
+    fn enter(names: &mut Vec<String>, binder: &String) {
+        names.push(binder.clone());
+    }
+
+    fn exit(names: &mut Vec<String>) {
+        names.pop();
+    }
+}
+
+/// Посетител на върховете на терм. Всеки метод получава обхвата на върха.
+pub trait TermVisitor<T: Scoped> {
+    fn visit_var(&mut self, _var: &T::Var, _scope: &T::Scope) {}
+    fn visit_apply(&mut self, _t1: &T, _t2: &T, _scope: &T::Scope) {}
+    fn visit_lambda(&mut self, _binder: &T::Binder, _body: &T, _scope: &T::Scope) {}
+}
+
+/// Обхожда терма в ляв префиксен ред, като извиква `visitor` за всеки връх.
+///
+/// # Пример
+///
+/// ```
+/// use hw::unnamed::Term;
+/// use hw::unnamed_term;
+/// use hw::visit::{walk, TermVisitor};
+///
+/// struct FreeVars(Vec<usize>);
+///
+/// impl TermVisitor<Term> for FreeVars {
+///     fn visit_var(&mut self, var: &usize, depth: &usize) {
+///         if var >= depth {
+///             self.0.push(var - depth);
+///         }
+///     }
+/// }
+///
+/// let mut free = FreeVars(Vec::new());
+/// walk(&unnamed_term!(λ 0 2 (λ 1 3)), &mut free);
+/// assert_eq!(free.0, vec![1, 1]);
+/// ```
+pub fn walk<T, V>(term: &T, visitor: &mut V)
+where
+    T: Scoped,
+    V: TermVisitor<T> + ?Sized,
+{
+    enum Frame<'a, T> {
+        Visit(&'a T),
+        Exit,
+    }
+
+    let mut scope = T::Scope::default();
+    let mut frames = vec![Frame::Visit(term)];
+
+    while let Some(frame) = frames.pop() {
+        let term = match frame {
+            Frame::Visit(term) => term,
+            Frame::Exit => {
+                T::exit(&mut scope);
+                continue;
+            },
+        };
+
+        match term.view() {
+            View::Var(x) => visitor.visit_var(x, &scope),
+            View::Apply(t1, t2) => {
+                visitor.visit_apply(t1, t2, &scope);
+                frames.push(Frame::Visit(t2));
+                frames.push(Frame::Visit(t1));
+            },
+            View::Lambda(binder, t) => {
+                visitor.visit_lambda(binder, t, &scope);
+                T::enter(&mut scope, binder);
+                frames.push(Frame::Exit);
+                frames.push(Frame::Visit(t));
+            },
+        }
+    }
+}
+
+/// Свиване на терм отдолу нагоре. Всеки метод получава резултатите за
+/// децата на върха и обхвата на самия връх.
+pub trait TermFolder<T: Scoped> {
+    type Output;
+
+    fn fold_var(&mut self, var: &T::Var, scope: &T::Scope) -> Self::Output;
+    fn fold_apply(&mut self, t1: Self::Output, t2: Self::Output, scope: &T::Scope)
+        -> Self::Output;
+    fn fold_lambda(&mut self, binder: &T::Binder, body: Self::Output, scope: &T::Scope)
+        -> Self::Output;
+}
+
+/// Свива терма с `folder`.
+///
+/// # Пример
+///
+/// ```
+/// use hw::named::Term;
+/// use hw::term;
+/// use hw::visit::{fold, TermFolder};
+///
+/// /// Заменя свободните променливи с `f x`.
+/// struct Wrap;
+///
+/// impl TermFolder<Term> for Wrap {
+///     type Output = Term;
+///
+///     fn fold_var(&mut self, var: &String, scope: &Vec<String>) -> Term {
+///         if scope.contains(var) {
+///             Term::var(var.clone())
+///         } else {
+///             Term::apply(Term::var("f"), Term::var(var.clone()))
+///         }
+///     }
+///
+///     fn fold_apply(&mut self, t1: Term, t2: Term, _: &Vec<String>) -> Term {
+///         Term::apply(t1, t2)
+///     }
+///
+///     fn fold_lambda(&mut self, binder: &String, body: Term, _: &Vec<String>) -> Term {
+///         Term::lambda(binder.clone(), body)
+///     }
+/// }
+///
+/// assert_eq!(fold(&term!(λ x. x y), &mut Wrap), term!(λ x. x (f y)));
+/// ```
+pub fn fold<T, F>(term: &T, folder: &mut F) -> F::Output
+where
+    T: Scoped,
+    F: TermFolder<T> + ?Sized,
+{
+    enum Frame<'a, T: LambdaTerm> {
+        Visit(&'a T),
+        Apply,
+        Lambda(&'a T::Binder),
+    }
+
+    let mut scope = T::Scope::default();
+    let mut frames = vec![Frame::Visit(term)];
+    let mut results = Vec::new();
+
+    while let Some(frame) = frames.pop() {
+        match frame {
+            Frame::Visit(term) => match term.view() {
+                View::Var(x) => results.push(folder.fold_var(x, &scope)),
+                View::Apply(t1, t2) => {
+                    frames.push(Frame::Apply);
+                    frames.push(Frame::Visit(t2));
+                    frames.push(Frame::Visit(t1));
+                },
+                View::Lambda(binder, t) => {
+                    T::enter(&mut scope, binder);
+                    frames.push(Frame::Lambda(binder));
+                    frames.push(Frame::Visit(t));
+                },
+            },
+            Frame::Apply => {
+                let t2 = results.pop().unwrap();
+                let t1 = results.pop().unwrap();
+                results.push(folder.fold_apply(t1, t2, &scope));
+            },
+            Frame::Lambda(binder) => {
+                T::exit(&mut scope);
+                let body = results.pop().unwrap();
+                results.push(folder.fold_lambda(binder, body, &scope));
+            },
+        }
+    }
+
+    results.pop().unwrap()
+}
+
+#[cfg(test)]
+mod tests;
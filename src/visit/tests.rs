@@ -0,0 +1,149 @@
+use super::{fold, walk, TermFolder, TermVisitor};
+use crate::named::Term as NamedTerm;
+use crate::unnamed::Term as UnnamedTerm;
+use crate::{term, unnamed_term};
+
+/// Записва обхвата на всяка променлива.
+#[derive(Default)]
+struct Scopes<S>(Vec<S>);
+
+impl TermVisitor<UnnamedTerm> for Scopes<(usize, usize)> {
+    fn visit_var(&mut self, var: &usize, depth: &usize) {
+        self.0.push((*var, *depth));
+    }
+}
+
+impl TermVisitor<NamedTerm> for Scopes<(String, Vec<String>)> {
+    fn visit_var(&mut self, var: &String, names: &Vec<String>) {
+        self.0.push((var.clone(), names.clone()));
+    }
+}
+
+#[test]
+fn walk_tracks_scope() {
+    let mut scopes = Scopes::default();
+    walk(&unnamed_term!(0 (λ 0 (λ 2)) 1), &mut scopes);
+    assert_eq!(scopes.0, vec![(0, 0), (0, 1), (2, 2), (1, 0)]);
+
+    let mut scopes = Scopes::default();
+    walk(&term!(λ x. (λ y. x y) x), &mut scopes);
+    let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        scopes.0,
+        vec![
+            (String::from("x"), names(&["x", "y"])),
+            (String::from("y"), names(&["x", "y"])),
+            (String::from("x"), names(&["x"])),
+        ]
+    );
+}
+
+/// Брои апликациите и абстракциите, като запомня най-големия обхват.
+#[derive(Default)]
+struct Counter {
+    applications: usize,
+    lambdas: usize,
+    max_depth: usize,
+}
+
+impl TermVisitor<UnnamedTerm> for Counter {
+    fn visit_apply(&mut self, _: &UnnamedTerm, _: &UnnamedTerm, _: &usize) {
+        self.applications += 1;
+    }
+
+    fn visit_lambda(&mut self, _: &(), _: &UnnamedTerm, depth: &usize) {
+        self.lambdas += 1;
+        self.max_depth = self.max_depth.max(depth + 1);
+    }
+}
+
+#[test]
+fn walk_visits_all_nodes() {
+    let mut counter = Counter::default();
+    walk(&unnamed_term!((λ λ 1 0) (λ 0) 2), &mut counter);
+
+    assert_eq!(
+        (counter.applications, counter.lambdas, counter.max_depth),
+        (3, 3, 2)
+    );
+}
+
+/// Заменя свободните променливи на безименен терм с индекси в контекст,
+/// разширен с `by` нови имена отпред.
+struct Shift {
+    by: usize,
+}
+
+impl TermFolder<UnnamedTerm> for Shift {
+    type Output = UnnamedTerm;
+
+    fn fold_var(&mut self, var: &usize, depth: &usize) -> UnnamedTerm {
+        if var < depth {
+            UnnamedTerm::var(*var)
+        } else {
+            UnnamedTerm::var(var + self.by)
+        }
+    }
+
+    fn fold_apply(&mut self, t1: UnnamedTerm, t2: UnnamedTerm, _: &usize) -> UnnamedTerm {
+        UnnamedTerm::apply(t1, t2)
+    }
+
+    fn fold_lambda(&mut self, _: &(), body: UnnamedTerm, _: &usize) -> UnnamedTerm {
+        UnnamedTerm::lambda(body)
+    }
+}
+
+#[test]
+fn fold_tracks_scope() {
+    let term = unnamed_term!(0 (λ 0 1 (λ 2 1 0)));
+    assert_eq!(fold(&term, &mut Shift { by: 3 }), unnamed_term!(3 (λ 0 4 (λ 5 1 0))));
+}
+
+/// Връща свободните променливи на именуван терм в реда на срещане.
+struct FreeVars;
+
+impl TermFolder<NamedTerm> for FreeVars {
+    type Output = Vec<String>;
+
+    fn fold_var(&mut self, var: &String, names: &Vec<String>) -> Vec<String> {
+        if names.contains(var) {
+            vec![]
+        } else {
+            vec![var.clone()]
+        }
+    }
+
+    fn fold_apply(&mut self, t1: Vec<String>, t2: Vec<String>, _: &Vec<String>) -> Vec<String> {
+        let mut result = t1;
+        for x in t2 {
+            if !result.contains(&x) {
+                result.push(x);
+            }
+        }
+
+        result
+    }
+
+    fn fold_lambda(&mut self, _: &String, body: Vec<String>, _: &Vec<String>) -> Vec<String> {
+        body
+    }
+}
+
+#[test]
+fn fold_named() {
+    assert_eq!(fold(&term!(λ x. y x (λ z. z w y)), &mut FreeVars), vec!["y", "w"]);
+    assert_eq!(fold(&term!(x (λ x. x)), &mut FreeVars), vec!["x"]);
+}
+
+#[test]
+fn deep_terms() {
+    let depth = 100_000;
+    let term = (0..depth).fold(UnnamedTerm::var(depth), |t, _| UnnamedTerm::lambda(t));
+
+    let mut counter = Counter::default();
+    walk(&term, &mut counter);
+    assert_eq!((counter.lambdas, counter.max_depth), (depth, depth));
+
+    assert_eq!(fold(&term, &mut Shift { by: 1 }).occurrences(1), 1);
+}